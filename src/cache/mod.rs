@@ -0,0 +1,318 @@
+#[cfg(test)]
+mod tests;
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs::{self, read_dir, remove_file},
+    io::{Cursor, ErrorKind},
+    path::{Path, PathBuf},
+    sync::Mutex,
+    time::SystemTime,
+};
+
+use ical::parser::vcard::component::VcardContact;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+
+use crate::{
+    config::Entry,
+    decode::decode_property,
+    extract_contacts, extract_entry_with,
+    filter::{ContactFilter, FILTERED_PROPERTIES},
+    input::{detect_format, parse_contacts},
+    report::{Diagnostic, Report},
+    writes_event_files, EventExtractorError, ExtractedEvent,
+};
+
+/// Properties of the contacts kept in the cache, besides those the filters
+/// are applied to.
+const CACHED_PROPERTIES: [&str; 3] = ["FN", "BDAY", "UID"];
+
+#[derive(Error, Debug)]
+pub enum CacheError {
+    #[error("serde_json error")]
+    SerdeJsonError(#[from] serde_json::Error),
+    #[error("std::io error")]
+    StdIoError(#[from] std::io::Error),
+}
+
+/// Contacts of the input files of a configuration entry as of the last run.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Cache {
+    /// Hash of the settings the files were parsed with.
+    pub settings: String,
+    /// Cached files by their path.
+    pub files: BTreeMap<PathBuf, CachedFile>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CachedFile {
+    pub modified: Option<SystemTime>,
+    /// SHA-256 hash of the content.
+    pub hash: String,
+    /// Contacts reduced to the properties the events are extracted from.
+    pub contacts: Vec<VcardContact>,
+}
+
+impl Cache {
+    /// Loads the cache of a configuration entry.
+    ///
+    /// A missing or unreadable cache, or one created with other settings, is
+    /// replaced by an empty cache.
+    pub fn load(path: &str, config_entry: &Entry) -> Cache {
+        let settings = settings_hash(config_entry);
+        let cache = match fs::read_to_string(path) {
+            Ok(text) => serde_json::from_str::<Cache>(&text).map_err(|e| e.to_string()),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Cache::default()),
+            Err(e) => Err(e.to_string()),
+        };
+
+        match cache {
+            Ok(cache) if cache.settings == settings => cache,
+            Ok(_) => Cache {
+                settings,
+                ..Default::default()
+            },
+            Err(e) => {
+                log::warn!("ignoring the cache \"{}\": {}", path, e);
+                Cache {
+                    settings,
+                    ..Default::default()
+                }
+            }
+        }
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), CacheError> {
+        Ok(fs::write(path, serde_json::to_string(self)?)?)
+    }
+
+    /// Extracts the events of all contacts of a configuration entry, parsing
+    /// only the input files which changed since the last run.
+    ///
+    /// Returns the events, the report and the UIDs of the contacts which were
+    /// removed since the last run.
+    pub fn extract_entry(
+        &mut self,
+        config_entry: &Entry,
+        years: &[i32],
+    ) -> Result<(Vec<ExtractedEvent>, Report, BTreeSet<String>), EventExtractorError> {
+        let filter = ContactFilter::new(config_entry)?;
+        let files = Mutex::new(BTreeMap::new());
+        let (events, report) = extract_entry_with(config_entry, years, &filter, |path| {
+            self.extract_file(config_entry, path, years, &filter, &files)
+        })?;
+        let previous_uids = contact_uids(&self.files);
+
+        self.files = files
+            .into_inner()
+            .expect("workers do not panic while holding the lock");
+
+        let removed = previous_uids
+            .difference(&contact_uids(&self.files))
+            .cloned()
+            .collect();
+
+        Ok((events, report, removed))
+    }
+
+    fn extract_file(
+        &self,
+        config_entry: &Entry,
+        path: &Path,
+        years: &[i32],
+        filter: &ContactFilter,
+        files: &Mutex<BTreeMap<PathBuf, CachedFile>>,
+    ) -> Result<(Vec<ExtractedEvent>, Report), EventExtractorError> {
+        let mut report = Report::default();
+        let mut events = Vec::new();
+        let modified = fs::metadata(path)
+            .and_then(|metadata| metadata.modified())
+            .ok();
+        let cached = self.files.get(path);
+
+        let (modified, hash, contacts) = match cached {
+            Some(cached) if modified.is_some() && cached.modified == modified => {
+                log::debug!("file \"{}\" is unchanged", path.to_string_lossy());
+                (modified, cached.hash.clone(), cached.contacts.clone())
+            }
+            _ => match fs::read(path) {
+                Ok(data) => {
+                    let hash = format!("{:x}", Sha256::digest(&data));
+
+                    match cached {
+                        Some(cached) if cached.hash == hash => {
+                            log::debug!("content of \"{}\" is unchanged", path.to_string_lossy());
+                            (modified, hash, cached.contacts.clone())
+                        }
+                        _ => (modified, hash, parse_file(config_entry, path, data)?),
+                    }
+                }
+                Err(e) => {
+                    let diagnostic = Diagnostic::UnreadableInput {
+                        path: path.to_path_buf(),
+                        message: e.to_string(),
+                    };
+
+                    log::warn!("{}", diagnostic);
+                    report.diagnostics.push(diagnostic);
+
+                    // the contacts of a file which cannot be read at the moment
+                    // are kept, so that their events are not removed
+                    match cached {
+                        Some(cached) => (
+                            cached.modified,
+                            cached.hash.clone(),
+                            cached.contacts.clone(),
+                        ),
+                        None => return Ok((events, report)),
+                    }
+                }
+            },
+        };
+
+        extract_contacts(
+            contacts.iter().cloned().map(Ok),
+            path,
+            config_entry,
+            years,
+            filter,
+            &mut events,
+            &mut report,
+        )?;
+        report.events = events.len();
+
+        files
+            .lock()
+            .expect("workers do not panic while holding the lock")
+            .insert(
+                path.to_path_buf(),
+                CachedFile {
+                    modified,
+                    hash,
+                    contacts,
+                },
+            );
+
+        Ok((events, report))
+    }
+}
+
+/// Extracts the events of a configuration entry like `extract_entry`, but
+/// only parses the input files which changed since the last run. The event
+/// files of contacts which were removed are deleted.
+pub fn extract_cached(
+    config_entry: &Entry,
+    cache_path: &str,
+    years: &[i32],
+) -> Result<(Vec<ExtractedEvent>, Report), EventExtractorError> {
+    let mut cache = Cache::load(cache_path, config_entry);
+    let (events, report, removed) = cache.extract_entry(config_entry, years)?;
+
+    if writes_event_files(config_entry) {
+        for uid in &removed {
+            let count = remove_event_files(config_entry, uid)?;
+
+            log::info!(
+                "removed {} event files of deleted contact \"{}\"",
+                count,
+                uid
+            );
+        }
+    }
+
+    cache.save(cache_path)?;
+
+    Ok((events, report))
+}
+
+/// Removes the event files of a contact from the output directory.
+pub fn remove_event_files(config_entry: &Entry, contact_uid: &str) -> std::io::Result<usize> {
+    let prefix = format!("{}_bday_", contact_uid);
+    let extension = format!(".{}", config_entry.output_format.extension());
+    let mut count = 0;
+
+    for entry in read_dir(&config_entry.output)? {
+        let path = entry?.path();
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+
+        if path.is_file() && name.starts_with(&prefix) && name.ends_with(&extension) {
+            remove_file(path)?;
+            count += 1;
+        }
+    }
+
+    Ok(count)
+}
+
+fn parse_file(
+    config_entry: &Entry,
+    path: &Path,
+    data: Vec<u8>,
+) -> Result<Vec<VcardContact>, EventExtractorError> {
+    log::info!("processing file \"{}\"", path.to_string_lossy());
+
+    let cached_properties = cached_properties(config_entry);
+    let mut buf = Cursor::new(data);
+    let format = detect_format(path, &mut buf)?;
+    log::debug!("detected format {:?}", format);
+
+    parse_contacts(Box::new(buf), format, config_entry)
+        .map(|contact| {
+            Ok(VcardContact {
+                properties: contact?
+                    .properties
+                    .into_iter()
+                    .filter(|property| cached_properties.contains(&property.name))
+                    .collect(),
+            })
+        })
+        .collect()
+}
+
+/// Decoded UIDs of all cached contacts.
+fn contact_uids(files: &BTreeMap<PathBuf, CachedFile>) -> BTreeSet<String> {
+    files
+        .values()
+        .flat_map(|file| &file.contacts)
+        .flat_map(|contact| &contact.properties)
+        .filter(|property| property.name == "UID")
+        .filter_map(|property| decode_property(property).ok()?.value)
+        .collect()
+}
+
+/// Names of the properties kept in the cache.
+fn cached_properties(config_entry: &Entry) -> BTreeSet<String> {
+    let filter = &config_entry.filter;
+
+    CACHED_PROPERTIES
+        .iter()
+        .chain(FILTERED_PROPERTIES.iter())
+        .map(|name| name.to_string())
+        .chain(
+            filter
+                .include_properties
+                .iter()
+                .chain(&filter.exclude_properties)
+                .map(|pattern| pattern.property.to_uppercase()),
+        )
+        .collect()
+}
+
+/// Hash of the settings affecting the parsing of the input files.
+fn settings_hash(config_entry: &Entry) -> String {
+    format!(
+        "{:x}",
+        Sha256::digest(format!(
+            "{} {:?} {:?} {:?}",
+            env!("CARGO_PKG_VERSION"),
+            config_entry.csv_columns,
+            config_entry.ldif_attributes,
+            cached_properties(config_entry)
+        ))
+    )
+}
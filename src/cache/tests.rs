@@ -0,0 +1,213 @@
+use std::fs::File;
+
+use crate::{
+    config::{CsvColumns, FilterOptions},
+    testing::vcard,
+    write_events,
+};
+
+use super::*;
+
+fn names(events: &[ExtractedEvent]) -> Vec<&str> {
+    let mut names: Vec<&str> = events.iter().map(|event| event.name.as_str()).collect();
+
+    names.dedup();
+    names
+}
+
+#[test]
+fn extract_entry_1() {
+    let input = tempfile::tempdir().unwrap();
+    let entry = Entry {
+        input: input.path().to_string_lossy().into(),
+        ..Default::default()
+    };
+    let mut cache = Cache::default();
+
+    fs::write(input.path().join("a.vcf"), vcard("a", "A")).unwrap();
+    fs::write(input.path().join("b.vcf"), vcard("b", "B")).unwrap();
+
+    let (events, report, removed) = cache.extract_entry(&entry, &[2023]).unwrap();
+    assert_eq!(names(&events), vec!["A", "B"]);
+    assert_eq!(report.contacts, 2);
+    assert!(removed.is_empty());
+    assert_eq!(
+        cache.files[&input.path().join("a.vcf")].contacts[0]
+            .properties
+            .iter()
+            .map(|property| property.name.as_str())
+            .collect::<Vec<_>>(),
+        vec!["UID", "FN", "BDAY"]
+    );
+
+    fs::write(input.path().join("a.vcf"), vcard("c", "C")).unwrap();
+    fs::remove_file(input.path().join("b.vcf")).unwrap();
+
+    let (events, report, removed) = cache.extract_entry(&entry, &[2023]).unwrap();
+    assert_eq!(names(&events), vec!["C"]);
+    assert_eq!(report.contacts, 1);
+    assert_eq!(removed, BTreeSet::from(["a".into(), "b".into()]));
+    assert_eq!(cache.files.len(), 1);
+}
+
+#[test]
+fn extract_entry_2() {
+    let input = tempfile::tempdir().unwrap();
+    let path = input.path().join("a.vcf");
+    let entry = Entry {
+        input: input.path().to_string_lossy().into(),
+        ..Default::default()
+    };
+    let mut cache = Cache::default();
+
+    fs::write(&path, vcard("a", "A")).unwrap();
+    cache.extract_entry(&entry, &[2023]).unwrap();
+
+    // the contacts of a file with an unchanged modification time are reused
+    let modified = fs::metadata(&path).unwrap().modified().unwrap();
+    fs::write(&path, vcard("a", "Changed")).unwrap();
+    File::options()
+        .write(true)
+        .open(&path)
+        .unwrap()
+        .set_modified(modified)
+        .unwrap();
+
+    let (events, _, _) = cache.extract_entry(&entry, &[2023, 2024]).unwrap();
+    assert_eq!(names(&events), vec!["A"]);
+    assert_eq!(events.len(), 2);
+}
+
+#[test]
+fn extract_entry_3() {
+    let input = tempfile::tempdir().unwrap();
+    let entry = Entry {
+        input: input.path().to_string_lossy().into(),
+        filter: FilterOptions {
+            include_groups: vec!["Family".into()],
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let mut cache = Cache::default();
+
+    fs::write(input.path().join("a.vcf"), vcard("a", "A")).unwrap();
+    fs::write(input.path().join("b.vcf"), vcard("b", "B")).unwrap();
+    fs::write(
+        input.path().join("groups.vcf"),
+        "BEGIN:VCARD\r\nVERSION:4.0\r\nKIND:group\r\nUID:family\r\nFN:Family\r\nMEMBER:urn:uuid:b\r\nEND:VCARD\r\n",
+    )
+    .unwrap();
+
+    let (events, _, _) = cache.extract_entry(&entry, &[2023]).unwrap();
+    assert_eq!(names(&events), vec!["B"]);
+
+    // the groups are found among the cached contacts
+    let (events, report, _) = cache.extract_entry(&entry, &[2023]).unwrap();
+    assert_eq!(names(&events), vec!["B"]);
+    assert_eq!(report.groups.len(), 1);
+}
+
+#[test]
+fn extract_file_1() {
+    let input = tempfile::tempdir().unwrap();
+    let path = input.path().join("a.vcf");
+    let entry = Entry {
+        input: input.path().to_string_lossy().into(),
+        ..Default::default()
+    };
+    let mut cache = Cache::default();
+
+    fs::write(&path, vcard("a", "A")).unwrap();
+    cache.extract_entry(&entry, &[2023]).unwrap();
+
+    // the contacts of a file which cannot be read are kept
+    fs::remove_file(&path).unwrap();
+    let files = Mutex::new(BTreeMap::new());
+    let (events, report) = cache
+        .extract_file(&entry, &path, &[2023], &ContactFilter::default(), &files)
+        .unwrap();
+
+    assert_eq!(names(&events), vec!["A"]);
+    assert!(matches!(
+        report.diagnostics[..],
+        [Diagnostic::UnreadableInput { .. }]
+    ));
+    assert_eq!(files.into_inner().unwrap().len(), 1);
+}
+
+#[test]
+fn extract_cached_1() {
+    let input = tempfile::tempdir().unwrap();
+    let output = tempfile::tempdir().unwrap();
+    let state = tempfile::tempdir().unwrap();
+    let cache_path = state
+        .path()
+        .join("cache.json")
+        .to_string_lossy()
+        .into_owned();
+    let entry = Entry {
+        input: input.path().to_string_lossy().into(),
+        output: output.path().to_string_lossy().into(),
+        cache: Some(cache_path.clone()),
+        ..Default::default()
+    };
+
+    fs::write(input.path().join("a.vcf"), vcard("a", "A")).unwrap();
+    fs::write(input.path().join("b.vcf"), vcard("b", "B")).unwrap();
+    fs::write(output.path().join("a_bday_notes.txt"), "").unwrap();
+
+    let (events, mut report) = extract_cached(&entry, &cache_path, &[2023, 2024]).unwrap();
+    assert_eq!(report.events, 4);
+    write_events(&entry, &events, &mut report).unwrap();
+    assert_eq!(Cache::load(&cache_path, &entry).files.len(), 2);
+
+    fs::remove_file(input.path().join("a.vcf")).unwrap();
+
+    let (events, mut report) = extract_cached(&entry, &cache_path, &[2023, 2024]).unwrap();
+    write_events(&entry, &events, &mut report).unwrap();
+
+    let mut files: Vec<String> = fs::read_dir(output.path())
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+        .collect();
+    files.sort();
+    assert_eq!(
+        files,
+        vec!["a_bday_notes.txt", "b_bday_2023.ics", "b_bday_2024.ics"]
+    );
+}
+
+#[test]
+fn load_1() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("cache.json").to_string_lossy().into_owned();
+    let entry = Entry::default();
+
+    assert!(Cache::load(&path, &entry).files.is_empty());
+
+    let mut cache = Cache::load(&path, &entry);
+    cache.files.insert(
+        "a.vcf".into(),
+        CachedFile {
+            modified: None,
+            hash: String::new(),
+            contacts: Vec::new(),
+        },
+    );
+    cache.save(&path).unwrap();
+    assert_eq!(Cache::load(&path, &entry).files.len(), 1);
+
+    // a cache created with other settings is discarded
+    let other_entry = Entry {
+        csv_columns: CsvColumns {
+            birthday: "Geburtstag".into(),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    assert!(Cache::load(&path, &other_entry).files.is_empty());
+
+    fs::write(&path, "invalid").unwrap();
+    assert!(Cache::load(&path, &entry).files.is_empty());
+}
@@ -0,0 +1,117 @@
+#[cfg(test)]
+mod tests;
+
+use std::collections::BTreeMap;
+
+use crate::{
+    dav::{member_name, send, DavClient, DavError, PROPFIND_ETAGS_BODY},
+    report::Diagnostic,
+};
+
+/// Result of uploading events to a CalDAV collection.
+#[derive(Debug, Default, PartialEq)]
+pub struct Upload {
+    pub created: usize,
+    pub updated: usize,
+    pub deleted: usize,
+    /// Resources which were changed on the server in the meantime and were
+    /// therefore not overwritten or deleted.
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+/// Lists the resources of the collection with their ETags.
+pub fn list(client: &DavClient) -> Result<BTreeMap<String, String>, DavError> {
+    let multistatus = client.multistatus("PROPFIND", "1", PROPFIND_ETAGS_BODY)?;
+
+    Ok(multistatus
+        .responses
+        .into_iter()
+        .filter_map(|response| {
+            let etag = response.properties.get("getetag")?;
+
+            Some((member_name(&response.href)?, etag.clone()))
+        })
+        .collect())
+}
+
+/// Creates a resource, or replaces it if it still has the given ETag.
+///
+/// Returns false if the precondition failed.
+pub fn put(
+    client: &DavClient,
+    name: &str,
+    body: &str,
+    etag: Option<&str>,
+) -> Result<bool, DavError> {
+    let request = client
+        .request("PUT", &client.resource_url(name))
+        .set("Content-Type", "text/calendar; charset=utf-8");
+    let request = match etag {
+        Some(etag) => request.set("If-Match", etag),
+        None => request.set("If-None-Match", "*"),
+    };
+
+    Ok(send(request, Some(body), &[200, 201, 204, 412])?.status() != 412)
+}
+
+/// Deletes a resource if it still has the given ETag.
+///
+/// Returns false if the precondition failed.
+pub fn delete(client: &DavClient, name: &str, etag: &str) -> Result<bool, DavError> {
+    let request = client
+        .request("DELETE", &client.resource_url(name))
+        .set("If-Match", etag);
+
+    Ok(send(request, None, &[200, 204, 404, 412])?.status() != 412)
+}
+
+/// Uploads the given resources (file name and iCalendar data) to the
+/// collection and, if `remove_stale` is set, deletes all other calendar
+/// resources.
+///
+/// Existing resources are only replaced or deleted if they were not changed
+/// since they were listed.
+pub fn upload(
+    client: &DavClient,
+    resources: &[(String, String)],
+    remove_stale: bool,
+) -> Result<Upload, DavError> {
+    let existing = list(client)?;
+    let mut upload = Upload::default();
+
+    for (name, body) in resources {
+        let etag = existing.get(name);
+
+        if put(client, name, body, etag.map(String::as_str))? {
+            match etag {
+                Some(_) => upload.updated += 1,
+                None => upload.created += 1,
+            }
+        } else {
+            upload.diagnostics.push(conflict(client, name));
+        }
+    }
+
+    if remove_stale {
+        for (name, etag) in &existing {
+            if name.ends_with(".ics") && !resources.iter().any(|(elem, _)| elem == name) {
+                if delete(client, name, etag)? {
+                    upload.deleted += 1;
+                } else {
+                    upload.diagnostics.push(conflict(client, name));
+                }
+            }
+        }
+    }
+
+    Ok(upload)
+}
+
+fn conflict(client: &DavClient, name: &str) -> Diagnostic {
+    let diagnostic = Diagnostic::UploadConflict {
+        url: client.resource_url(name),
+    };
+
+    log::warn!("{}", diagnostic);
+    diagnostic
+}
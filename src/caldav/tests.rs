@@ -0,0 +1,216 @@
+use std::{
+    sync::{Arc, Mutex},
+    thread,
+};
+
+use tiny_http::{Header, Request, Server};
+
+use super::*;
+use crate::{
+    config::Credentials,
+    dav::{decode_path_segment, encode_path_segment},
+};
+
+type Resources = Arc<Mutex<BTreeMap<String, (String, String)>>>;
+
+/// Minimal CalDAV collection at "/calendars/test/" keeping its resources in
+/// memory.
+struct StandIn {
+    server: Arc<Server>,
+    url: String,
+    resources: Resources,
+    authorizations: Arc<Mutex<Vec<String>>>,
+}
+
+impl StandIn {
+    fn start(resources: &[(&str, &str)]) -> StandIn {
+        let server = Arc::new(Server::http("127.0.0.1:0").unwrap());
+        let url = format!(
+            "http://{}/calendars/test",
+            server.server_addr().to_ip().unwrap()
+        );
+        let resources: Resources = Arc::new(Mutex::new(
+            resources
+                .iter()
+                .map(|(name, etag)| (name.to_string(), (etag.to_string(), String::new())))
+                .collect(),
+        ));
+        let authorizations = Arc::new(Mutex::new(Vec::new()));
+
+        let stand_in = StandIn {
+            server: server.clone(),
+            url,
+            resources: resources.clone(),
+            authorizations: authorizations.clone(),
+        };
+
+        thread::spawn(move || {
+            for request in server.incoming_requests() {
+                if let Some(authorization) = find_header(&request, "Authorization") {
+                    authorizations.lock().unwrap().push(authorization);
+                }
+                handle(request, &resources);
+            }
+        });
+
+        stand_in
+    }
+}
+
+impl Drop for StandIn {
+    fn drop(&mut self) {
+        self.server.unblock();
+    }
+}
+
+fn find_header(request: &Request, name: &'static str) -> Option<String> {
+    request
+        .headers()
+        .iter()
+        .find(|header| header.field.equiv(name))
+        .map(|header| header.value.to_string())
+}
+
+fn handle(mut request: Request, resources: &Resources) {
+    let mut resources = resources.lock().unwrap();
+    let name = request
+        .url()
+        .strip_prefix("/calendars/test/")
+        .map(decode_path_segment)
+        .unwrap_or_default();
+    let current = resources.get(&name).map(|(etag, _)| etag.clone());
+    let if_match = find_header(&request, "If-Match");
+    let if_none_match = find_header(&request, "If-None-Match");
+    let precondition_failed = match (&current, &if_match, &if_none_match) {
+        (_, Some(if_match), _) => current.as_ref() != Some(if_match),
+        (Some(_), None, Some(_)) => true,
+        _ => false,
+    };
+
+    let status = match request.method().as_str() {
+        "PROPFIND" => {
+            let body = format!(
+                "<?xml version=\"1.0\"?><multistatus xmlns=\"DAV:\"><response><href>/calendars/test/</href><propstat><prop/></propstat></response>{}</multistatus>",
+                resources
+                    .iter()
+                    .map(|(name, (etag, _))| format!(
+                        "<response><href>/calendars/test/{}</href><propstat><prop><getetag>{}</getetag></prop></propstat></response>",
+                        encode_path_segment(name),
+                        etag
+                    ))
+                    .collect::<String>()
+            );
+            let _ = request.respond(tiny_http::Response::from_string(body).with_status_code(207));
+            return;
+        }
+        _ if precondition_failed => 412,
+        "PUT" => {
+            let mut body = String::new();
+
+            request.as_reader().read_to_string(&mut body).unwrap();
+            let etag = format!("\"{}\"", body.len());
+            resources.insert(name, (etag, body));
+            if current.is_some() {
+                204
+            } else {
+                201
+            }
+        }
+        "DELETE" => match resources.remove(&name) {
+            Some(_) => 204,
+            None => 404,
+        },
+        _ => 405,
+    };
+
+    let _ = request.respond(
+        tiny_http::Response::empty(status)
+            .with_header(Header::from_bytes("Content-Length", "0").unwrap()),
+    );
+}
+
+#[test]
+fn upload_1() {
+    let stand_in = StandIn::start(&[("a.ics", "\"1\""), ("stale.ics", "\"2\"")]);
+    let client = DavClient::new(
+        &stand_in.url,
+        Some(&Credentials {
+            username: "user".into(),
+            password: Some("secret".into()),
+            ..Default::default()
+        }),
+    )
+    .unwrap();
+
+    assert_eq!(
+        upload(
+            &client,
+            &[
+                ("a.ics".into(), "BEGIN:VCALENDAR".into()),
+                ("b c.ics".into(), "BEGIN:VCALENDAR".into())
+            ],
+            true
+        )
+        .unwrap(),
+        Upload {
+            created: 1,
+            updated: 1,
+            deleted: 1,
+            diagnostics: Vec::new()
+        }
+    );
+
+    let resources = stand_in.resources.lock().unwrap();
+    assert_eq!(
+        resources.keys().collect::<Vec<_>>(),
+        vec!["a.ics", "b c.ics"]
+    );
+    assert_eq!(resources["a.ics"].1, "BEGIN:VCALENDAR");
+    assert!(stand_in
+        .authorizations
+        .lock()
+        .unwrap()
+        .iter()
+        .all(|authorization| authorization == "Basic dXNlcjpzZWNyZXQ="));
+}
+
+#[test]
+fn upload_2() {
+    let stand_in = StandIn::start(&[("stale.ics", "\"2\""), ("other.txt", "\"3\"")]);
+    let client = DavClient::new(&stand_in.url, None).unwrap();
+
+    assert_eq!(
+        upload(
+            &client,
+            &[("a.ics".into(), "BEGIN:VCALENDAR".into())],
+            false
+        )
+        .unwrap(),
+        Upload {
+            created: 1,
+            ..Default::default()
+        }
+    );
+    assert_eq!(stand_in.resources.lock().unwrap().len(), 3);
+
+    assert_eq!(
+        upload(&client, &[("a.ics".into(), "BEGIN:VCALENDAR".into())], true).unwrap(),
+        Upload {
+            updated: 1,
+            deleted: 1,
+            ..Default::default()
+        }
+    );
+    assert!(stand_in.resources.lock().unwrap().contains_key("other.txt"));
+}
+
+#[test]
+fn put_conflict() {
+    let stand_in = StandIn::start(&[("a.ics", "\"1\"")]);
+    let client = DavClient::new(&stand_in.url, None).unwrap();
+
+    assert!(!put(&client, "a.ics", "changed", None).unwrap());
+    assert!(!put(&client, "a.ics", "changed", Some("\"0\"")).unwrap());
+    assert!(!delete(&client, "a.ics", "\"0\"").unwrap());
+    assert_eq!(stand_in.resources.lock().unwrap()["a.ics"].0, "\"1\"");
+}
@@ -0,0 +1,251 @@
+#[cfg(test)]
+mod tests;
+
+use std::{collections::BTreeMap, fs, io::ErrorKind};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{
+    config::Entry,
+    dav::{member_name, DavClient, DavError, PROPFIND_ETAGS_BODY},
+    ser::escape_xml,
+};
+
+const PROPFIND_VERSION_BODY: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<d:propfind xmlns:d="DAV:" xmlns:cs="http://calendarserver.org/ns/"><d:prop><cs:getctag/><d:sync-token/></d:prop></d:propfind>
+"#;
+
+#[derive(Error, Debug)]
+pub enum CarddavError {
+    #[error("WebDAV error: {}", .0)]
+    DavError(#[from] DavError),
+    #[error("serde_json error")]
+    SerdeJsonError(#[from] serde_json::Error),
+    #[error("std::io error")]
+    StdIoError(#[from] std::io::Error),
+}
+
+/// vCards of an address book as of the last synchronization.
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct SyncState {
+    pub sync_token: Option<String>,
+    pub ctag: Option<String>,
+    /// vCards by their href.
+    pub cards: BTreeMap<String, Card>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Card {
+    pub etag: String,
+    pub data: String,
+}
+
+/// Number of vCards fetched and removed by a synchronization.
+#[derive(Debug, Default, PartialEq)]
+pub struct SyncSummary {
+    pub fetched: usize,
+    pub removed: usize,
+}
+
+/// hrefs of the vCards changed since the last synchronization.
+struct Changes {
+    changed: Vec<String>,
+    removed: Vec<String>,
+    sync_token: Option<String>,
+}
+
+/// Fetches the vCards of the CardDAV address book given as input of a
+/// configuration entry and returns them with their hrefs.
+///
+/// If the entry has a `carddav_state` file, only the vCards changed since
+/// the last run are fetched.
+pub fn fetch_address_book(entry: &Entry) -> Result<Vec<(String, String)>, CarddavError> {
+    let client = DavClient::new(&entry.input, entry.input_credentials.as_ref())?;
+    let mut state = match &entry.carddav_state {
+        Some(path) => load_state(path)?,
+        None => SyncState::default(),
+    };
+
+    let summary = synchronize(&client, &mut state)?;
+    log::info!(
+        "synchronized \"{}\": {} vCards fetched, {} removed",
+        entry.input,
+        summary.fetched,
+        summary.removed
+    );
+
+    if let Some(path) = &entry.carddav_state {
+        fs::write(path, serde_json::to_string(&state)?)?;
+    }
+
+    Ok(state
+        .cards
+        .into_iter()
+        .map(|(href, card)| (href, card.data))
+        .collect())
+}
+
+/// Brings the state up to date with the address book.
+///
+/// Nothing is fetched if the ctag or the sync token of the address book did
+/// not change. Otherwise, the changes are requested with the sync token
+/// (RFC 6578) or, if the server does not support it, determined by
+/// comparing the ETags of all vCards. Only new and changed vCards are
+/// fetched.
+pub fn synchronize(client: &DavClient, state: &mut SyncState) -> Result<SyncSummary, CarddavError> {
+    let version = client.multistatus("PROPFIND", "0", PROPFIND_VERSION_BODY)?;
+    let properties = version
+        .responses
+        .into_iter()
+        .next()
+        .map(|response| response.properties)
+        .unwrap_or_default();
+    let ctag = properties.get("getctag").filter(|ctag| !ctag.is_empty());
+    let sync_token = properties
+        .get("sync-token")
+        .filter(|sync_token| !sync_token.is_empty());
+
+    if (ctag.is_some() && ctag == state.ctag.as_ref())
+        || (sync_token.is_some() && sync_token == state.sync_token.as_ref())
+    {
+        log::debug!("address book \"{}\" is unchanged", client.collection());
+        return Ok(SyncSummary::default());
+    }
+
+    let changes = match (&state.sync_token, sync_token) {
+        (Some(previous), Some(_)) => match sync_collection(client, previous) {
+            Err(DavError::UnexpectedStatus(_, _, 403 | 409)) => {
+                log::info!("sync token was rejected, comparing all vCards");
+                None
+            }
+            changes => Some(changes?),
+        },
+        _ => None,
+    };
+    let changes = match changes {
+        Some(changes) => changes,
+        None => compare_etags(client, state, sync_token.cloned())?,
+    };
+
+    let mut summary = SyncSummary::default();
+
+    for href in changes.removed {
+        if state.cards.remove(&href).is_some() {
+            summary.removed += 1;
+        }
+    }
+
+    if !changes.changed.is_empty() {
+        for (href, card) in multiget(client, &changes.changed)? {
+            state.cards.insert(href, card);
+            summary.fetched += 1;
+        }
+    }
+
+    state.ctag = ctag.cloned();
+    state.sync_token = changes.sync_token;
+
+    Ok(summary)
+}
+
+/// Requests the changes since the given sync token.
+fn sync_collection(client: &DavClient, sync_token: &str) -> Result<Changes, DavError> {
+    let body = format!(
+        r#"<?xml version="1.0" encoding="utf-8"?>
+<d:sync-collection xmlns:d="DAV:"><d:sync-token>{}</d:sync-token><d:sync-level>1</d:sync-level><d:prop><d:getetag/></d:prop></d:sync-collection>
+"#,
+        escape_xml(sync_token)
+    );
+    let multistatus = client.multistatus("REPORT", "0", &body)?;
+    let mut changed = Vec::new();
+    let mut removed = Vec::new();
+
+    for response in multistatus.responses {
+        if member_name(&response.href).is_none() {
+            continue;
+        }
+
+        match response.status {
+            Some(404) => removed.push(response.href),
+            _ => changed.push(response.href),
+        }
+    }
+
+    Ok(Changes {
+        changed,
+        removed,
+        sync_token: multistatus.sync_token,
+    })
+}
+
+/// Determines the changes by comparing the ETags of all vCards with the
+/// state.
+fn compare_etags(
+    client: &DavClient,
+    state: &SyncState,
+    sync_token: Option<String>,
+) -> Result<Changes, DavError> {
+    let multistatus = client.multistatus("PROPFIND", "1", PROPFIND_ETAGS_BODY)?;
+    let mut etags = BTreeMap::new();
+
+    for response in multistatus.responses {
+        if let (Some(_), Some(etag)) = (
+            member_name(&response.href),
+            response.properties.get("getetag"),
+        ) {
+            etags.insert(response.href, etag.clone());
+        }
+    }
+
+    let changed = etags
+        .iter()
+        .filter(|(href, etag)| state.cards.get(*href).map(|card| &card.etag) != Some(etag))
+        .map(|(href, _)| href.clone())
+        .collect();
+    let removed = state
+        .cards
+        .keys()
+        .filter(|href| !etags.contains_key(*href))
+        .cloned()
+        .collect();
+
+    Ok(Changes {
+        changed,
+        removed,
+        sync_token,
+    })
+}
+
+/// Fetches the given vCards (RFC 6352 addressbook-multiget).
+fn multiget(client: &DavClient, hrefs: &[String]) -> Result<Vec<(String, Card)>, DavError> {
+    let body = format!(
+        r#"<?xml version="1.0" encoding="utf-8"?>
+<c:addressbook-multiget xmlns:d="DAV:" xmlns:c="urn:ietf:params:xml:ns:carddav"><d:prop><d:getetag/><c:address-data/></d:prop>{}</c:addressbook-multiget>
+"#,
+        hrefs
+            .iter()
+            .map(|href| format!("<d:href>{}</d:href>", escape_xml(href)))
+            .collect::<String>()
+    );
+    let multistatus = client.multistatus("REPORT", "1", &body)?;
+
+    Ok(multistatus
+        .responses
+        .into_iter()
+        .filter_map(|mut response| {
+            let data = response.properties.remove("address-data")?;
+            let etag = response.properties.remove("getetag").unwrap_or_default();
+
+            Some((response.href, Card { etag, data }))
+        })
+        .collect())
+}
+
+fn load_state(path: &str) -> Result<SyncState, CarddavError> {
+    match fs::read_to_string(path) {
+        Ok(text) => Ok(serde_json::from_str(&text)?),
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(SyncState::default()),
+        Err(e) => Err(e.into()),
+    }
+}
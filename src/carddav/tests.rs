@@ -0,0 +1,336 @@
+use std::{
+    sync::{Arc, Mutex},
+    thread,
+};
+
+use tiny_http::{Request, Response, Server};
+
+use crate::testing::vcard;
+
+use super::*;
+
+const COLLECTION: &str = "/addressbooks/test/";
+
+/// Address book keeping a log of its changes to answer sync requests.
+#[derive(Default)]
+struct AddressBook {
+    supports_sync: bool,
+    version: usize,
+    cards: BTreeMap<String, (String, String)>,
+    /// Version, href and whether the vCard was removed.
+    changes: Vec<(usize, String, bool)>,
+    /// hrefs requested by addressbook-multiget.
+    fetched: Vec<String>,
+}
+
+impl AddressBook {
+    fn put(&mut self, name: &str, data: &str) {
+        let href = format!("{}{}", COLLECTION, name);
+
+        self.version += 1;
+        self.cards
+            .insert(href.clone(), (format!("\"{}\"", self.version), data.into()));
+        self.changes.push((self.version, href, false));
+    }
+
+    fn remove(&mut self, name: &str) {
+        let href = format!("{}{}", COLLECTION, name);
+
+        self.version += 1;
+        self.cards.remove(&href);
+        self.changes.push((self.version, href, true));
+    }
+}
+
+struct MockServer {
+    server: Arc<Server>,
+    url: String,
+    address_book: Arc<Mutex<AddressBook>>,
+}
+
+impl MockServer {
+    fn start(supports_sync: bool) -> MockServer {
+        let server = Arc::new(Server::http("127.0.0.1:0").unwrap());
+        let url = format!(
+            "http://{}{}",
+            server.server_addr().to_ip().unwrap(),
+            COLLECTION
+        );
+        let address_book = Arc::new(Mutex::new(AddressBook {
+            supports_sync,
+            ..Default::default()
+        }));
+
+        let mock_server = MockServer {
+            server: server.clone(),
+            url,
+            address_book: address_book.clone(),
+        };
+
+        thread::spawn(move || {
+            for request in server.incoming_requests() {
+                handle(request, &mut address_book.lock().unwrap());
+            }
+        });
+
+        mock_server
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        self.server.unblock();
+    }
+}
+
+fn response(href: &str, properties: &str) -> String {
+    format!(
+        "<response><href>{}</href><propstat><prop>{}</prop><status>HTTP/1.1 200 OK</status></propstat></response>",
+        href, properties
+    )
+}
+
+fn handle(mut request: Request, address_book: &mut AddressBook) {
+    let mut body = String::new();
+    request.as_reader().read_to_string(&mut body).unwrap();
+
+    let depth = request
+        .headers()
+        .iter()
+        .find(|header| header.field.equiv("Depth"))
+        .map(|header| header.value.to_string());
+    let mut sync_token = None;
+
+    let responses = match (request.method().as_str(), depth.as_deref()) {
+        ("PROPFIND", Some("0")) => response(
+            COLLECTION,
+            &format!(
+                "<getctag xmlns=\"http://calendarserver.org/ns/\">ctag-{}</getctag>{}",
+                address_book.version,
+                if address_book.supports_sync {
+                    format!("<sync-token>token-{}</sync-token>", address_book.version)
+                } else {
+                    String::new()
+                }
+            ),
+        ),
+        ("PROPFIND", _) => address_book
+            .cards
+            .iter()
+            .map(|(href, (etag, _))| response(href, &format!("<getetag>{}</getetag>", etag)))
+            .collect(),
+        ("REPORT", _) if body.contains("sync-collection") => {
+            let version = body
+                .split("token-")
+                .nth(1)
+                .and_then(|rest| rest.split('<').next())
+                .and_then(|version| version.parse::<usize>().ok());
+            let version = match version {
+                Some(version) if address_book.supports_sync => version,
+                _ => {
+                    let _ = request.respond(Response::empty(403));
+                    return;
+                }
+            };
+            let mut changes = BTreeMap::new();
+
+            for (_, href, removed) in address_book
+                .changes
+                .iter()
+                .filter(|(change_version, _, _)| *change_version > version)
+            {
+                changes.insert(href.clone(), *removed);
+            }
+
+            sync_token = Some(format!("token-{}", address_book.version));
+            changes
+                .into_iter()
+                .map(|(href, removed)| match removed {
+                    true => format!(
+                        "<response><href>{}</href><status>HTTP/1.1 404 Not Found</status></response>",
+                        href
+                    ),
+                    false => response(
+                        &href,
+                        &format!("<getetag>{}</getetag>", address_book.cards[&href].0),
+                    ),
+                })
+                .collect()
+        }
+        ("REPORT", _) => {
+            let document = roxmltree::Document::parse(&body).unwrap();
+            let hrefs: Vec<String> = document
+                .descendants()
+                .filter(|node| node.has_tag_name(("DAV:", "href")))
+                .filter_map(|node| node.text())
+                .map(String::from)
+                .collect();
+
+            address_book.fetched.extend(hrefs.iter().cloned());
+            hrefs
+                .iter()
+                .filter_map(|href| address_book.cards.get(href).map(|card| (href, card)))
+                .map(|(href, (etag, data))| {
+                    response(
+                        href,
+                        &format!(
+                            "<getetag>{}</getetag><address-data xmlns=\"urn:ietf:params:xml:ns:carddav\">{}</address-data>",
+                            etag, data
+                        ),
+                    )
+                })
+                .collect()
+        }
+        _ => {
+            let _ = request.respond(Response::empty(405));
+            return;
+        }
+    };
+
+    let _ = request.respond(
+        Response::from_string(format!(
+            "<?xml version=\"1.0\"?><multistatus xmlns=\"DAV:\">{}{}</multistatus>",
+            responses,
+            sync_token
+                .map(|sync_token| format!("<sync-token>{}</sync-token>", sync_token))
+                .unwrap_or_default()
+        ))
+        .with_status_code(207),
+    );
+}
+
+#[test]
+fn synchronize_1() {
+    let mock_server = MockServer::start(true);
+    let client = DavClient::new(&mock_server.url, None).unwrap();
+    let mut state = SyncState::default();
+
+    {
+        let mut address_book = mock_server.address_book.lock().unwrap();
+        address_book.put("a.vcf", &vcard("a", "Test Person"));
+        address_book.put("b.vcf", &vcard("b", "Test Person"));
+    }
+
+    assert_eq!(
+        synchronize(&client, &mut state).unwrap(),
+        SyncSummary {
+            fetched: 2,
+            removed: 0
+        }
+    );
+    assert_eq!(state.sync_token.as_deref(), Some("token-2"));
+    assert_eq!(state.cards.len(), 2);
+
+    // unchanged address book
+    assert_eq!(
+        synchronize(&client, &mut state).unwrap(),
+        SyncSummary::default()
+    );
+
+    {
+        let mut address_book = mock_server.address_book.lock().unwrap();
+        address_book.fetched.clear();
+        address_book.put("b.vcf", &vcard("b2", "Test Person"));
+        address_book.put("c.vcf", &vcard("c", "Test Person"));
+        address_book.remove("a.vcf");
+    }
+
+    assert_eq!(
+        synchronize(&client, &mut state).unwrap(),
+        SyncSummary {
+            fetched: 2,
+            removed: 1
+        }
+    );
+    assert_eq!(
+        mock_server.address_book.lock().unwrap().fetched,
+        vec!["/addressbooks/test/b.vcf", "/addressbooks/test/c.vcf"]
+    );
+    assert_eq!(state.sync_token.as_deref(), Some("token-5"));
+    assert_eq!(
+        state.cards.keys().collect::<Vec<_>>(),
+        vec!["/addressbooks/test/b.vcf", "/addressbooks/test/c.vcf"]
+    );
+    assert!(state.cards["/addressbooks/test/b.vcf"]
+        .data
+        .contains("UID:b2"));
+}
+
+#[test]
+fn synchronize_2() {
+    let mock_server = MockServer::start(false);
+    let client = DavClient::new(&mock_server.url, None).unwrap();
+    let mut state = SyncState::default();
+
+    {
+        let mut address_book = mock_server.address_book.lock().unwrap();
+        address_book.put("a.vcf", &vcard("a", "Test Person"));
+        address_book.put("b.vcf", &vcard("b", "Test Person"));
+    }
+
+    assert_eq!(synchronize(&client, &mut state).unwrap().fetched, 2);
+    assert_eq!(state.ctag.as_deref(), Some("ctag-2"));
+    assert_eq!(state.sync_token, None);
+
+    {
+        let mut address_book = mock_server.address_book.lock().unwrap();
+        address_book.fetched.clear();
+        address_book.put("a.vcf", &vcard("a2", "Test Person"));
+        address_book.remove("b.vcf");
+    }
+
+    assert_eq!(
+        synchronize(&client, &mut state).unwrap(),
+        SyncSummary {
+            fetched: 1,
+            removed: 1
+        }
+    );
+    assert_eq!(
+        mock_server.address_book.lock().unwrap().fetched,
+        vec!["/addressbooks/test/a.vcf"]
+    );
+}
+
+#[test]
+fn fetch_address_book_1() {
+    let mock_server = MockServer::start(true);
+    let dir = tempfile::tempdir().unwrap();
+    let entry = Entry {
+        input: mock_server.url.clone(),
+        carddav_state: Some(dir.path().join("state.json").to_string_lossy().into()),
+        ..Default::default()
+    };
+
+    mock_server
+        .address_book
+        .lock()
+        .unwrap()
+        .put("a.vcf", &vcard("a", "Test Person"));
+
+    let cards = fetch_address_book(&entry).unwrap();
+    assert_eq!(cards.len(), 1);
+    assert_eq!(cards[0].0, "/addressbooks/test/a.vcf");
+
+    // the second run uses the saved state
+    mock_server.address_book.lock().unwrap().fetched.clear();
+    assert_eq!(fetch_address_book(&entry).unwrap(), cards);
+    assert!(mock_server.address_book.lock().unwrap().fetched.is_empty());
+
+    let (events, report) = crate::extract_entry(&entry, &[2023]).unwrap();
+    assert_eq!(report.contacts, 1);
+    assert_eq!(events[0].uid, "a_bday_2023");
+}
+
+#[test]
+fn carddav_error_1() {
+    assert_eq!(
+        crate::EventExtractorError::from(CarddavError::from(DavError::UnexpectedStatus(
+            "REPORT".into(),
+            "https://example.com/addressbooks/test/".into(),
+            404
+        )))
+        .to_string(),
+        "CardDAV error: WebDAV error: REPORT \"https://example.com/addressbooks/test/\" failed with status 404"
+    )
+}
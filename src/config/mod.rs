@@ -1,7 +1,9 @@
 use serde::Deserialize;
-use std::fs;
+use std::{fs, path::Path};
 use thiserror::Error;
 
+use crate::parallel::default_jobs;
+
 #[cfg(test)]
 mod tests;
 
@@ -20,11 +22,346 @@ pub struct Config {
     pub entries: Vec<Entry>,
 }
 
-#[derive(Deserialize, Debug, PartialEq)]
+#[derive(Deserialize, Debug, PartialEq, Clone)]
 pub struct Entry {
+    /// Name of the calendar served by the `serve` command. Defaults to the
+    /// file name of the output without its extension.
+    #[serde(default)]
+    pub name: Option<String>,
     pub input: String,
     pub output: String,
     pub remove_files: bool,
+    /// Language tag (e.g. "de" or "en-GB") used to select between
+    /// alternative property values.
+    pub preferred_language: Option<String>,
+    /// Log a diagnostic for every contact with conflicting birthdays.
+    #[serde(default)]
+    pub report_date_conflicts: bool,
+    /// Descend into subdirectories of the input directory.
+    #[serde(default)]
+    pub recursive: bool,
+    /// File extensions of the input files.
+    #[serde(default = "default_extensions")]
+    pub extensions: Vec<String>,
+    /// Glob patterns, relative to the input directory, of the files to
+    /// process. All files are processed if no pattern is given.
+    #[serde(default)]
+    pub include: Vec<String>,
+    /// Glob patterns, relative to the input directory, of the files to skip.
+    #[serde(default)]
+    pub exclude: Vec<String>,
+    /// Mapping of the columns of CSV input files.
+    #[serde(default)]
+    pub csv_columns: CsvColumns,
+    /// Mapping of the attributes of LDIF input files.
+    #[serde(default)]
+    pub ldif_attributes: LdifAttributes,
+    #[serde(default)]
+    pub output_format: OutputFormat,
+    /// Options of the HTML output.
+    #[serde(default)]
+    pub html: HtmlOptions,
+    /// Options of the Atom feed output.
+    #[serde(default)]
+    pub feed: FeedOptions,
+    /// Credentials for a CardDAV address book given as input.
+    #[serde(default)]
+    pub input_credentials: Option<Credentials>,
+    /// File keeping the vCards of a CardDAV input between runs, so that only
+    /// changed vCards are fetched.
+    #[serde(default)]
+    pub carddav_state: Option<String>,
+    /// Credentials for a CalDAV collection given as output.
+    #[serde(default)]
+    pub output_credentials: Option<Credentials>,
+    /// File caching the contacts of the input files between runs, so that
+    /// only changed files are parsed.
+    #[serde(default)]
+    pub cache: Option<String>,
+    /// Number of input files parsed in parallel. Defaults to the number of
+    /// available CPUs.
+    #[serde(default)]
+    pub jobs: Option<usize>,
+    /// What to do if the output is locked by another run.
+    #[serde(default)]
+    pub lock: LockBehavior,
+    /// Contacts whose events are generated.
+    #[serde(default)]
+    pub filter: FilterOptions,
+    /// Additional outputs with the events of the members of groups.
+    #[serde(default)]
+    pub group_outputs: Vec<GroupOutput>,
+    /// Merge duplicate contacts, also with those of the other deduplicated
+    /// entries with the same output.
+    #[serde(default)]
+    pub deduplicate: bool,
+}
+
+#[derive(Deserialize, Debug, PartialEq, Clone, Copy, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFormat {
+    #[default]
+    ICalendar,
+    JCal,
+    XCal,
+    /// Single CSV file with one row per event.
+    Csv,
+    /// Single JSON Lines file with one object per event.
+    #[serde(rename = "jsonl")]
+    JsonLines,
+    /// Single HTML file with an agenda of the events.
+    Html,
+    /// Single Atom feed with the upcoming events.
+    Atom,
+}
+
+impl Entry {
+    /// Number of input files parsed in parallel.
+    pub fn jobs(&self) -> usize {
+        self.jobs.unwrap_or_else(default_jobs)
+    }
+
+    /// Name of the calendar of the entry.
+    pub fn calendar_name(&self) -> String {
+        if let Some(name) = &self.name {
+            return name.clone();
+        }
+
+        [&self.output, &self.input]
+            .iter()
+            .filter_map(|path| Path::new(path).file_stem())
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .find(|stem| !stem.is_empty() && stem != "-")
+            .unwrap_or_else(|| "calendar".into())
+    }
+}
+
+impl OutputFormat {
+    /// Extension of the output files.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            OutputFormat::ICalendar => "ics",
+            OutputFormat::JCal => "json",
+            OutputFormat::XCal => "xml",
+            OutputFormat::Csv => "csv",
+            OutputFormat::JsonLines => "jsonl",
+            OutputFormat::Html => "html",
+            OutputFormat::Atom => "atom",
+        }
+    }
+}
+
+/// Behaviour if the output of an entry is locked by another run.
+#[derive(Deserialize, Debug, PartialEq, Clone, Copy, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum LockBehavior {
+    /// Wait until the other run is finished.
+    #[default]
+    Wait,
+    /// Skip the entry.
+    Skip,
+    /// Fail with an error.
+    Fail,
+}
+
+/// Filters selecting the contacts whose events are generated.
+///
+/// A contact has to match at least one entry of every non-empty `include_`
+/// list and no entry of the `exclude_` lists.
+#[derive(Deserialize, Debug, PartialEq, Clone)]
+pub struct FilterOptions {
+    /// Categories (`CATEGORIES`), compared case-insensitively.
+    #[serde(default)]
+    pub include_categories: Vec<String>,
+    #[serde(default)]
+    pub exclude_categories: Vec<String>,
+    /// Kinds of contacts (`KIND`) to skip; contacts without a kind are
+    /// individuals.
+    #[serde(default = "default_exclude_kinds")]
+    pub exclude_kinds: Vec<String>,
+    /// Groups (contacts of the kind "group"), given by their UID or name,
+    /// listing the contact as `MEMBER`.
+    #[serde(default)]
+    pub include_groups: Vec<String>,
+    #[serde(default)]
+    pub exclude_groups: Vec<String>,
+    /// Regular expressions matched against the values of properties.
+    #[serde(default)]
+    pub include_properties: Vec<PropertyPattern>,
+    #[serde(default)]
+    pub exclude_properties: Vec<PropertyPattern>,
+}
+
+#[derive(Deserialize, Debug, PartialEq, Clone)]
+pub struct PropertyPattern {
+    pub property: String,
+    pub regex: String,
+}
+
+/// Output of the events of the members of a group, written in the output
+/// format of the entry.
+#[derive(Deserialize, Debug, PartialEq, Clone)]
+pub struct GroupOutput {
+    /// UID or name of the group.
+    pub group: String,
+    pub output: String,
+}
+
+#[derive(Deserialize, Debug, PartialEq, Clone)]
+pub struct CsvColumns {
+    /// Columns making up the name; non-empty values are joined by a space.
+    #[serde(default = "default_name_columns")]
+    pub name: Vec<String>,
+    /// Column holding a unique identifier. If it is not given, the identifier
+    /// is derived from the name.
+    #[serde(default)]
+    pub uid: Option<String>,
+    #[serde(default = "default_birthday_column")]
+    pub birthday: String,
+}
+
+#[derive(Deserialize, Debug, PartialEq, Clone)]
+pub struct LdifAttributes {
+    #[serde(default = "default_name_attribute")]
+    pub name: String,
+    /// Attribute holding a unique identifier. If an entry does not have it,
+    /// its distinguished name is used instead.
+    #[serde(default = "default_uid_attribute")]
+    pub uid: String,
+    #[serde(default = "default_birthday_attribute")]
+    pub birthday: String,
+}
+
+#[derive(Deserialize, Debug, PartialEq, Clone)]
+pub struct HtmlOptions {
+    #[serde(default = "default_html_title")]
+    pub title: String,
+    /// Number of months shown, starting with the current month.
+    #[serde(default = "default_html_months")]
+    pub months: u32,
+    /// Template file replacing the built-in template.
+    #[serde(default)]
+    pub template: Option<String>,
+}
+
+#[derive(Deserialize, Debug, PartialEq, Clone)]
+pub struct FeedOptions {
+    #[serde(default = "default_html_title")]
+    pub title: String,
+    /// Number of days to look ahead.
+    #[serde(default = "default_feed_days")]
+    pub days: i64,
+}
+
+/// Credentials for basic authentication. The password is given directly,
+/// read from a file or taken from an environment variable.
+#[derive(Deserialize, Debug, PartialEq, Clone, Default)]
+pub struct Credentials {
+    pub username: String,
+    #[serde(default)]
+    pub password: Option<String>,
+    #[serde(default)]
+    pub password_file: Option<String>,
+    #[serde(default)]
+    pub password_env: Option<String>,
+}
+
+impl Credentials {
+    pub fn password(&self) -> Result<String, ConfigError> {
+        match (&self.password, &self.password_file, &self.password_env) {
+            (Some(password), _, _) => Ok(password.clone()),
+            (None, Some(password_file), _) => Ok(fs::read_to_string(password_file)?
+                .trim_end_matches(['\r', '\n'])
+                .to_string()),
+            (None, None, Some(password_env)) => std::env::var(password_env).map_err(|_| {
+                ConfigError::ConfigError(format!(
+                    "environment variable \"{}\" is not set",
+                    password_env
+                ))
+            }),
+            (None, None, None) => Err(ConfigError::ConfigError(format!(
+                "no password given for user \"{}\"",
+                self.username
+            ))),
+        }
+    }
+}
+
+fn default_extensions() -> Vec<String> {
+    vec!["vcf".into()]
+}
+
+fn default_name_columns() -> Vec<String> {
+    vec!["Name".into()]
+}
+
+fn default_birthday_column() -> String {
+    "Birthday".into()
+}
+
+fn default_name_attribute() -> String {
+    "cn".into()
+}
+
+fn default_uid_attribute() -> String {
+    "entryUUID".into()
+}
+
+fn default_birthday_attribute() -> String {
+    "birthDate".into()
+}
+
+fn default_html_title() -> String {
+    "Birthdays".into()
+}
+
+fn default_html_months() -> u32 {
+    12
+}
+
+fn default_feed_days() -> i64 {
+    14
+}
+
+impl Default for Entry {
+    fn default() -> Self {
+        Entry {
+            name: None,
+            input: String::new(),
+            output: String::new(),
+            remove_files: false,
+            preferred_language: None,
+            report_date_conflicts: false,
+            recursive: false,
+            extensions: default_extensions(),
+            include: Vec::new(),
+            exclude: Vec::new(),
+            csv_columns: CsvColumns::default(),
+            ldif_attributes: LdifAttributes::default(),
+            output_format: OutputFormat::default(),
+            html: HtmlOptions::default(),
+            feed: FeedOptions::default(),
+            input_credentials: None,
+            carddav_state: None,
+            output_credentials: None,
+            cache: None,
+            jobs: None,
+            lock: LockBehavior::default(),
+            filter: FilterOptions::default(),
+            group_outputs: Vec::new(),
+            deduplicate: false,
+        }
+    }
+}
+
+impl Default for CsvColumns {
+    fn default() -> Self {
+        CsvColumns {
+            name: default_name_columns(),
+            uid: None,
+            birthday: default_birthday_column(),
+        }
+    }
 }
 
 impl Config {
@@ -34,3 +371,50 @@ impl Config {
         )?)
     }
 }
+
+impl Default for LdifAttributes {
+    fn default() -> Self {
+        LdifAttributes {
+            name: default_name_attribute(),
+            uid: default_uid_attribute(),
+            birthday: default_birthday_attribute(),
+        }
+    }
+}
+
+fn default_exclude_kinds() -> Vec<String> {
+    vec!["org".into(), "group".into()]
+}
+
+impl Default for FilterOptions {
+    fn default() -> Self {
+        FilterOptions {
+            include_categories: Vec::new(),
+            exclude_categories: Vec::new(),
+            exclude_kinds: default_exclude_kinds(),
+            include_groups: Vec::new(),
+            exclude_groups: Vec::new(),
+            include_properties: Vec::new(),
+            exclude_properties: Vec::new(),
+        }
+    }
+}
+
+impl Default for HtmlOptions {
+    fn default() -> Self {
+        HtmlOptions {
+            title: default_html_title(),
+            months: default_html_months(),
+            template: None,
+        }
+    }
+}
+
+impl Default for FeedOptions {
+    fn default() -> Self {
+        FeedOptions {
+            title: default_html_title(),
+            days: default_feed_days(),
+        }
+    }
+}
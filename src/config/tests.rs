@@ -14,10 +14,171 @@ fn parse_test_1() {
         serde_json::from_str::<Config>(text).unwrap(),
         Config {
             entries: vec![Entry {
+                name: None,
                 input: "/path/input".into(),
                 output: "/path/output".into(),
-                remove_files: true
+                remove_files: true,
+                preferred_language: None,
+                report_date_conflicts: false,
+                recursive: false,
+                extensions: vec!["vcf".into()],
+                include: Vec::new(),
+                exclude: Vec::new(),
+                csv_columns: CsvColumns {
+                    name: vec!["Name".into()],
+                    uid: None,
+                    birthday: "Birthday".into()
+                },
+                ldif_attributes: LdifAttributes {
+                    name: "cn".into(),
+                    uid: "entryUUID".into(),
+                    birthday: "birthDate".into()
+                },
+                output_format: OutputFormat::ICalendar,
+                html: HtmlOptions {
+                    title: "Birthdays".into(),
+                    months: 12,
+                    template: None
+                },
+                feed: FeedOptions {
+                    title: "Birthdays".into(),
+                    days: 14
+                },
+                input_credentials: None,
+                carddav_state: None,
+                output_credentials: None,
+                cache: None,
+                jobs: None,
+                lock: LockBehavior::Wait,
+                filter: FilterOptions {
+                    include_categories: Vec::new(),
+                    exclude_categories: Vec::new(),
+                    exclude_kinds: vec!["org".into(), "group".into()],
+                    include_groups: Vec::new(),
+                    exclude_groups: Vec::new(),
+                    include_properties: Vec::new(),
+                    exclude_properties: Vec::new()
+                },
+                group_outputs: Vec::new(),
+                deduplicate: false
             }]
         }
     )
 }
+
+#[test]
+fn parse_test_2() {
+    let text = r#"
+        {
+            "entries": [
+                {
+                    "input": "/path/input",
+                    "output": "/path/output",
+                    "remove_files": false,
+                    "preferred_language": "de",
+                    "report_date_conflicts": true,
+                    "recursive": true,
+                    "extensions": ["vcf", "vcard"],
+                    "include": ["contacts/**"],
+                    "exclude": ["**/archive/**"],
+                    "output_format": "jcal"
+                }
+            ]
+        }
+    "#;
+
+    assert_eq!(
+        serde_json::from_str::<Config>(text).unwrap(),
+        Config {
+            entries: vec![Entry {
+                input: "/path/input".into(),
+                output: "/path/output".into(),
+                remove_files: false,
+                preferred_language: Some("de".into()),
+                report_date_conflicts: true,
+                recursive: true,
+                extensions: vec!["vcf".into(), "vcard".into()],
+                include: vec!["contacts/**".into()],
+                exclude: vec!["**/archive/**".into()],
+                output_format: OutputFormat::JCal,
+                ..Default::default()
+            }]
+        }
+    )
+}
+
+#[test]
+fn parse_test_3() {
+    let text = r#"
+        {
+            "entries": [
+                {
+                    "input": "/path/contacts.csv",
+                    "output": "/path/output",
+                    "remove_files": false,
+                    "csv_columns": {
+                        "name": ["First Name", "Last Name"],
+                        "uid": "Id"
+                    }
+                }
+            ]
+        }
+    "#;
+
+    assert_eq!(
+        serde_json::from_str::<Config>(text).unwrap(),
+        Config {
+            entries: vec![Entry {
+                input: "/path/contacts.csv".into(),
+                output: "/path/output".into(),
+                remove_files: false,
+                csv_columns: CsvColumns {
+                    name: vec!["First Name".into(), "Last Name".into()],
+                    uid: Some("Id".into()),
+                    birthday: "Birthday".into()
+                },
+                ..Default::default()
+            }]
+        }
+    )
+}
+
+#[test]
+fn calendar_name_1() {
+    let mut entry = Entry {
+        input: "/path/input/contacts.vcf".into(),
+        output: "/path/birthdays.html".into(),
+        ..Default::default()
+    };
+
+    assert_eq!(entry.calendar_name(), "birthdays");
+
+    entry.output = String::new();
+    assert_eq!(entry.calendar_name(), "contacts");
+
+    entry.name = Some("family".into());
+    assert_eq!(entry.calendar_name(), "family");
+}
+
+#[test]
+fn credentials_password_1() {
+    let dir = tempfile::tempdir().unwrap();
+    let password_file = dir.path().join("password");
+
+    fs::write(&password_file, "secret\n").unwrap();
+
+    let mut credentials = Credentials {
+        username: "user".into(),
+        password_file: Some(password_file.to_string_lossy().into()),
+        ..Default::default()
+    };
+    assert_eq!(credentials.password().unwrap(), "secret");
+
+    credentials.password = Some("other".into());
+    assert_eq!(credentials.password().unwrap(), "other");
+
+    credentials.password = None;
+    credentials.password_file = None;
+    credentials.password_env = Some("EVENT_EXTRACTOR_TEST_UNSET".into());
+    assert!(credentials.password().is_err());
+}
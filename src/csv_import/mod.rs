@@ -0,0 +1,153 @@
+#[cfg(test)]
+mod tests;
+
+use std::{collections::BTreeSet, io::Read};
+
+use chrono::NaiveDate;
+use csv::StringRecord;
+use ical::{parser::vcard::component::VcardContact, property::Property};
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+
+use crate::{config::CsvColumns, jcard::escape_text};
+
+#[derive(Error, Debug)]
+pub enum CsvImportError {
+    #[error("column \"{}\" not found", .0)]
+    ColumnNotFound(String),
+    #[error("csv error")]
+    CsvError(#[from] csv::Error),
+}
+
+/// Parses a CSV file (e.g. exported from Google Contacts or Outlook) into
+/// contacts, using the given mapping of columns to properties.
+///
+/// Rows without a name are skipped. Dates which cannot be parsed are logged
+/// and ignored. Without a UID column, the identifier is derived from the name
+/// and the birthday; rows sharing both get the row number appended.
+pub fn parse_csv(
+    reader: impl Read,
+    columns: &CsvColumns,
+) -> Result<Vec<VcardContact>, CsvImportError> {
+    let mut reader = csv::ReaderBuilder::new().flexible(true).from_reader(reader);
+    let headers = reader.headers()?.clone();
+    let find_column = |name: &str| {
+        headers
+            .iter()
+            .position(|header| header.trim() == name)
+            .ok_or(CsvImportError::ColumnNotFound(name.into()))
+    };
+
+    let name_columns = columns
+        .name
+        .iter()
+        .map(|name| find_column(name))
+        .collect::<Result<Vec<usize>, CsvImportError>>()?;
+    let uid_column = columns.uid.as_deref().map(find_column).transpose()?;
+    let birthday_column = find_column(&columns.birthday)?;
+    let mut contacts = Vec::new();
+    let mut derived_uids = BTreeSet::new();
+
+    for (index, record) in reader.records().enumerate() {
+        let record = record?;
+        let name = name_columns
+            .iter()
+            .filter_map(|&column| field(&record, column))
+            .collect::<Vec<&str>>()
+            .join(" ");
+
+        if name.is_empty() {
+            log::warn!("skipping row {} without a name", index + 1);
+            continue;
+        }
+
+        let birthday = field(&record, birthday_column);
+        let uid = match uid_column.and_then(|column| field(&record, column)) {
+            Some(uid) => uid.to_string(),
+            None => {
+                let uid = derive_uid(&name, birthday.unwrap_or_default());
+
+                match derived_uids.insert(uid.clone()) {
+                    true => uid,
+                    false => {
+                        log::warn!(
+                            "row {} has the same name and birthday as an earlier row",
+                            index + 1
+                        );
+                        format!("{}-{}", uid, index + 1)
+                    }
+                }
+            }
+        };
+        let mut properties = vec![
+            Property {
+                name: "FN".into(),
+                params: None,
+                value: Some(escape_text(&name)),
+            },
+            Property {
+                name: "UID".into(),
+                params: None,
+                value: Some(uid),
+            },
+        ];
+
+        if let Some(value) = birthday {
+            match parse_date(value) {
+                Some(date) => properties.push(Property {
+                    name: "BDAY".into(),
+                    params: Some(vec![("VALUE".into(), vec!["DATE".into()])]),
+                    value: Some(date),
+                }),
+                None => log::warn!(
+                    "ignoring unexpected date \"{}\" of \"{}\" in row {}",
+                    value,
+                    name,
+                    index + 1
+                ),
+            }
+        }
+
+        contacts.push(VcardContact { properties });
+    }
+
+    Ok(contacts)
+}
+
+/// Converts a date like "1990-10-05", "--10-05" or "10/05/1990" (month
+/// first) into the vCard basic format.
+pub(crate) fn parse_date(value: &str) -> Option<String> {
+    if let Some(month_day) = value.strip_prefix("--") {
+        // validate against a leap year to accept February 29th
+        let date = NaiveDate::parse_from_str(&format!("2000-{}", month_day), "%Y-%m-%d")
+            .or_else(|_| NaiveDate::parse_from_str(&format!("2000{}", month_day), "%Y%m%d"))
+            .ok()?;
+
+        return Some(date.format("--%m%d").to_string());
+    }
+
+    ["%Y-%m-%d", "%m/%d/%Y", "%Y%m%d"]
+        .iter()
+        .find_map(|format| NaiveDate::parse_from_str(value, format).ok())
+        .map(|date| date.format("%Y%m%d").to_string())
+}
+
+/// Derives an identifier from the name and the birthday of a contact.
+fn derive_uid(name: &str, birthday: &str) -> String {
+    let hash = Sha256::digest(format!("{}\n{}", name, birthday).as_bytes());
+
+    format!(
+        "csv-{}",
+        hash[..16]
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect::<String>()
+    )
+}
+
+fn field(record: &StringRecord, column: usize) -> Option<&str> {
+    record
+        .get(column)
+        .map(|value| value.trim())
+        .filter(|value| !value.is_empty())
+}
@@ -0,0 +1,117 @@
+use super::*;
+
+#[test]
+fn parse_csv_1() {
+    let text = "Name,Birthday,Anniversary,Notes\n\
+                \"Doe, J.\",1990-10-05,--06-01,\n\
+                Test Person,--02-29,,\n\
+                ,1980-01-01,,\n";
+
+    assert_eq!(
+        format!(
+            "{:?}",
+            parse_csv(text.as_bytes(), &CsvColumns::default()).unwrap()
+        ),
+        format!(
+            "{:?}",
+            vec![
+                VcardContact {
+                    properties: vec![
+                        Property {
+                            name: "FN".into(),
+                            params: None,
+                            value: Some("Doe\\, J.".into())
+                        },
+                        Property {
+                            name: "UID".into(),
+                            params: None,
+                            value: Some(derive_uid("Doe, J.", "1990-10-05"))
+                        },
+                        Property {
+                            name: "BDAY".into(),
+                            params: Some(vec![("VALUE".into(), vec!["DATE".into()])]),
+                            value: Some("19901005".into())
+                        },
+                    ]
+                },
+                VcardContact {
+                    properties: vec![
+                        Property {
+                            name: "FN".into(),
+                            params: None,
+                            value: Some("Test Person".into())
+                        },
+                        Property {
+                            name: "UID".into(),
+                            params: None,
+                            value: Some(derive_uid("Test Person", "--02-29"))
+                        },
+                        Property {
+                            name: "BDAY".into(),
+                            params: Some(vec![("VALUE".into(), vec!["DATE".into()])]),
+                            value: Some("--0229".into())
+                        },
+                    ]
+                }
+            ]
+        )
+    )
+}
+
+#[test]
+fn parse_csv_2() {
+    let text = "First Name,Last Name,Id,Birthday\nTest,Person,test_uid,10/05/1990\n";
+    let contacts = parse_csv(
+        text.as_bytes(),
+        &CsvColumns {
+            name: vec!["First Name".into(), "Last Name".into()],
+            uid: Some("Id".into()),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+
+    assert_eq!(
+        contacts[0]
+            .properties
+            .iter()
+            .map(|property| property.value.clone().unwrap())
+            .collect::<Vec<String>>(),
+        vec![
+            "Test Person".to_string(),
+            "test_uid".to_string(),
+            "19901005".to_string()
+        ]
+    )
+}
+
+#[test]
+fn parse_csv_3() {
+    let text = "Name,Birthday\nJane,1990-10-05\nJane,1985-12-24\nJane,1990-10-05\n";
+    let uids: BTreeSet<String> = parse_csv(text.as_bytes(), &CsvColumns::default())
+        .unwrap()
+        .iter()
+        .flat_map(|contact| &contact.properties)
+        .filter(|property| property.name == "UID")
+        .filter_map(|property| property.value.clone())
+        .collect();
+
+    assert_eq!(uids.len(), 3);
+    assert!(uids.contains(&format!("{}-3", derive_uid("Jane", "1990-10-05"))));
+}
+
+#[test]
+fn parse_csv_missing_column() {
+    assert!(parse_csv("Name\nTest Person\n".as_bytes(), &CsvColumns::default()).is_err())
+}
+
+#[test]
+fn parse_date_1() {
+    assert_eq!(parse_date("1990-10-05"), Some("19901005".into()));
+    assert_eq!(parse_date("--10-05"), Some("--1005".into()));
+    assert_eq!(parse_date("10/05/1990"), Some("19901005".into()));
+    assert_eq!(parse_date("19901005"), Some("19901005".into()));
+    assert_eq!(parse_date("--1005"), Some("--1005".into()));
+    assert_eq!(parse_date("--02-30"), None);
+    assert_eq!(parse_date("5. Oktober"), None);
+}
@@ -0,0 +1,128 @@
+#[cfg(test)]
+mod tests;
+
+use std::{
+    sync::mpsc::{channel, Receiver, RecvTimeoutError},
+    thread,
+    time::Duration,
+};
+
+use chrono::{DateTime, Datelike, Local, NaiveDate, TimeZone};
+use signal_hook::{
+    consts::{SIGHUP, SIGINT, SIGTERM},
+    iterator::Signals,
+};
+
+use crate::{
+    config::{Config, ConfigError},
+    merge_entries, process_merged, EventExtractorError,
+};
+
+/// Longest time slept at once, so that a run is delayed by at most this
+/// long when the system clock jumps, e.g. after a suspend.
+const MAX_SLEEP: Duration = Duration::from_secs(60);
+
+/// Reason the daemon woke up.
+#[derive(Debug, PartialEq)]
+pub enum Wakeup {
+    /// The next run is due.
+    Scheduled,
+    /// SIGHUP was received.
+    Reload,
+    /// SIGTERM or SIGINT was received.
+    Terminate,
+}
+
+/// Start of the next year, when the years for which events are generated
+/// change.
+pub fn next_year_start(now: DateTime<Local>) -> DateTime<Local> {
+    let midnight = NaiveDate::from_ymd_opt(now.year() + 1, 1, 1)
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+        .expect("January 1st is a valid date");
+
+    // midnight does not exist if the daylight saving time starts at midnight
+    midnight
+        .and_local_timezone(Local)
+        .earliest()
+        .unwrap_or_else(|| Local.from_utc_datetime(&midnight))
+}
+
+/// Time of the run after the given one: after the interval or at the start
+/// of the next year, whichever comes first.
+pub fn next_run(last_run: DateTime<Local>, interval: Duration) -> DateTime<Local> {
+    let year_start = next_year_start(last_run);
+
+    chrono::Duration::from_std(interval)
+        .ok()
+        .and_then(|interval| last_run.checked_add_signed(interval))
+        .map_or(year_start, |next_run| next_run.min(year_start))
+}
+
+/// Waits until the deadline or until a signal is received.
+pub fn wait(signals: &Receiver<i32>, deadline: DateTime<Local>) -> Wakeup {
+    loop {
+        let remaining = match (deadline - Local::now()).to_std() {
+            Ok(remaining) if !remaining.is_zero() => remaining,
+            _ => return Wakeup::Scheduled,
+        };
+
+        match signals.recv_timeout(remaining.min(MAX_SLEEP)) {
+            Ok(SIGHUP) => return Wakeup::Reload,
+            Ok(_) | Err(RecvTimeoutError::Disconnected) => return Wakeup::Terminate,
+            Err(RecvTimeoutError::Timeout) => {}
+        }
+    }
+}
+
+/// Processes the configuration entries repeatedly: after every interval and
+/// at the start of every year.
+///
+/// On SIGHUP the configuration is reloaded and the entries are processed
+/// immediately. On SIGTERM or SIGINT the daemon stops; a run in progress is
+/// finished first.
+pub fn daemon(
+    mut load_config: impl FnMut() -> Result<Config, ConfigError>,
+    interval: Duration,
+) -> Result<(), EventExtractorError> {
+    let mut signals = Signals::new([SIGTERM, SIGINT, SIGHUP])?;
+    let handle = signals.handle();
+    let (sender, receiver) = channel();
+
+    thread::spawn(move || {
+        for signal in signals.forever() {
+            if sender.send(signal).is_err() {
+                break;
+            }
+        }
+    });
+
+    let mut config = load_config()?;
+
+    loop {
+        for entries in merge_entries(&config.entries) {
+            match process_merged(&entries) {
+                Ok(report) => log::info!("{}", report),
+                Err(e) => log::error!("{}", e),
+            }
+        }
+
+        let deadline = next_run(Local::now(), interval);
+        log::info!("next run at {}", deadline.format("%Y-%m-%d %H:%M:%S"));
+
+        match wait(&receiver, deadline) {
+            Wakeup::Scheduled => {}
+            Wakeup::Reload => match load_config() {
+                Ok(new_config) => {
+                    log::info!("configuration reloaded");
+                    config = new_config;
+                }
+                Err(e) => log::error!("reloading the configuration failed: {}", e),
+            },
+            Wakeup::Terminate => {
+                log::info!("terminating");
+                handle.close();
+                return Ok(());
+            }
+        }
+    }
+}
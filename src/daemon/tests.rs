@@ -0,0 +1,58 @@
+use std::sync::mpsc::Sender;
+
+use super::*;
+
+fn local(year: i32, month: u32, day: u32, hour: u32) -> DateTime<Local> {
+    Local
+        .with_ymd_and_hms(year, month, day, hour, 0, 0)
+        .earliest()
+        .unwrap()
+}
+
+#[test]
+fn next_year_start_1() {
+    assert_eq!(
+        next_year_start(local(2023, 6, 15, 12)),
+        local(2024, 1, 1, 0)
+    );
+    assert_eq!(
+        next_year_start(local(2023, 12, 31, 23)),
+        local(2024, 1, 1, 0)
+    );
+    assert_eq!(next_year_start(local(2024, 1, 1, 0)), local(2025, 1, 1, 0));
+}
+
+#[test]
+fn next_run_1() {
+    let hour = Duration::from_secs(3600);
+
+    assert_eq!(
+        next_run(local(2023, 6, 15, 12), hour),
+        local(2023, 6, 15, 13)
+    );
+    assert_eq!(
+        next_run(local(2023, 12, 31, 22), 6 * hour),
+        local(2024, 1, 1, 0)
+    );
+    assert_eq!(
+        next_run(local(2023, 6, 15, 12), Duration::MAX),
+        local(2024, 1, 1, 0)
+    );
+}
+
+#[test]
+fn wait_1() {
+    let (sender, receiver): (Sender<i32>, _) = channel();
+    let deadline = Local::now() + chrono::Duration::hours(1);
+
+    sender.send(SIGHUP).unwrap();
+    assert_eq!(wait(&receiver, deadline), Wakeup::Reload);
+
+    sender.send(SIGTERM).unwrap();
+    assert_eq!(wait(&receiver, deadline), Wakeup::Terminate);
+
+    assert_eq!(wait(&receiver, Local::now()), Wakeup::Scheduled);
+
+    drop(sender);
+    assert_eq!(wait(&receiver, deadline), Wakeup::Terminate);
+}
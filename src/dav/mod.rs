@@ -0,0 +1,257 @@
+#[cfg(test)]
+mod tests;
+
+use std::collections::BTreeMap;
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use thiserror::Error;
+use ureq::{Agent, OrAnyStatus, Request, Response};
+
+use crate::config::{ConfigError, Credentials};
+
+/// PROPFIND request body for the ETags of the members of a collection.
+pub(crate) const PROPFIND_ETAGS_BODY: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<d:propfind xmlns:d="DAV:"><d:prop><d:getetag/></d:prop></d:propfind>
+"#;
+
+#[derive(Error, Debug)]
+pub enum DavError {
+    #[error("request failed: {}", .0)]
+    RequestFailed(String),
+    #[error("{} \"{}\" failed with status {}", .0, .1, .2)]
+    UnexpectedStatus(String, String, u16),
+    #[error("invalid response: {}", .0)]
+    InvalidResponse(String),
+    #[error("configuration error")]
+    ConfigError(#[from] ConfigError),
+    #[error("std::io error")]
+    StdIoError(#[from] std::io::Error),
+}
+
+impl From<ureq::Transport> for DavError {
+    fn from(e: ureq::Transport) -> Self {
+        DavError::RequestFailed(e.to_string())
+    }
+}
+
+/// Client for a single WebDAV collection, e.g. a CalDAV calendar or a
+/// CardDAV address book.
+pub struct DavClient {
+    agent: Agent,
+    collection: String,
+    authorization: Option<String>,
+}
+
+/// Response of a multistatus response body.
+#[derive(Debug, Default, PartialEq)]
+pub struct DavResponse {
+    pub href: String,
+    /// Status of the response itself, e.g. 404 for members removed since
+    /// the last synchronization.
+    pub status: Option<u16>,
+    /// Properties found, by their local name.
+    pub properties: BTreeMap<String, String>,
+}
+
+/// Parsed multistatus response body.
+#[derive(Debug, Default, PartialEq)]
+pub struct Multistatus {
+    pub responses: Vec<DavResponse>,
+    pub sync_token: Option<String>,
+}
+
+/// Checks whether an input or output is the URL of a WebDAV collection.
+pub fn is_collection_url(location: &str) -> bool {
+    location.starts_with("http://") || location.starts_with("https://")
+}
+
+impl DavClient {
+    pub fn new(collection: &str, credentials: Option<&Credentials>) -> Result<Self, DavError> {
+        let mut collection = collection.to_string();
+
+        if !collection.ends_with('/') {
+            collection.push('/');
+        }
+
+        let authorization = match credentials {
+            Some(credentials) => Some(format!(
+                "Basic {}",
+                STANDARD.encode(format!(
+                    "{}:{}",
+                    credentials.username,
+                    credentials.password()?
+                ))
+            )),
+            None => None,
+        };
+
+        Ok(DavClient {
+            agent: Agent::new(),
+            collection,
+            authorization,
+        })
+    }
+
+    /// URL of the collection, ending with a slash.
+    pub fn collection(&self) -> &str {
+        &self.collection
+    }
+
+    /// URL of a resource in the collection.
+    pub fn resource_url(&self, name: &str) -> String {
+        format!("{}{}", self.collection, encode_path_segment(name))
+    }
+
+    /// Creates an authenticated request.
+    pub fn request(&self, method: &str, url: &str) -> Request {
+        let request = self.agent.request(method, url);
+
+        match &self.authorization {
+            Some(authorization) => request.set("Authorization", authorization),
+            None => request,
+        }
+    }
+
+    /// Sends an XML request (e.g. PROPFIND or REPORT) to the collection and
+    /// parses the multistatus response.
+    pub fn multistatus(
+        &self,
+        method: &str,
+        depth: &str,
+        body: &str,
+    ) -> Result<Multistatus, DavError> {
+        let request = self
+            .request(method, &self.collection)
+            .set("Depth", depth)
+            .set("Content-Type", "application/xml; charset=utf-8");
+        let response = send(request, Some(body), &[207])?;
+
+        parse_multistatus(&response.into_string()?)
+    }
+}
+
+/// Sends a request and checks the status of the response.
+pub fn send(request: Request, body: Option<&str>, expected: &[u16]) -> Result<Response, DavError> {
+    let method = request.method().to_string();
+    let response = match body {
+        Some(body) => request.send_string(body),
+        None => request.call(),
+    }
+    .or_any_status()?;
+
+    if expected.contains(&response.status()) {
+        Ok(response)
+    } else {
+        Err(DavError::UnexpectedStatus(
+            method,
+            response.get_url().into(),
+            response.status(),
+        ))
+    }
+}
+
+/// Parses a multistatus response body (RFC 4918).
+///
+/// Only properties with a successful status are kept.
+pub fn parse_multistatus(text: &str) -> Result<Multistatus, DavError> {
+    let document =
+        roxmltree::Document::parse(text).map_err(|e| DavError::InvalidResponse(e.to_string()))?;
+    let root = document.root_element();
+    let mut multistatus = Multistatus {
+        sync_token: child_text(root, "sync-token"),
+        ..Default::default()
+    };
+
+    for response in root
+        .children()
+        .filter(|node| node.has_tag_name(("DAV:", "response")))
+    {
+        let mut dav_response = DavResponse {
+            href: child_text(response, "href")
+                .ok_or_else(|| DavError::InvalidResponse("response without \"href\"".into()))?,
+            status: child_text(response, "status").and_then(|status| parse_status(&status)),
+            ..Default::default()
+        };
+
+        for propstat in response
+            .children()
+            .filter(|node| node.has_tag_name(("DAV:", "propstat")))
+        {
+            let successful = child_text(propstat, "status")
+                .and_then(|status| parse_status(&status))
+                .is_none_or(|status| (200..300).contains(&status));
+
+            for prop in propstat
+                .children()
+                .filter(|node| successful && node.has_tag_name(("DAV:", "prop")))
+            {
+                for property in prop.children().filter(|node| node.is_element()) {
+                    dav_response.properties.insert(
+                        property.tag_name().name().to_string(),
+                        property.text().unwrap_or_default().trim().to_string(),
+                    );
+                }
+            }
+        }
+
+        multistatus.responses.push(dav_response);
+    }
+
+    Ok(multistatus)
+}
+
+/// Name of the member referenced by an href, or `None` for collections.
+pub fn member_name(href: &str) -> Option<String> {
+    match href.rsplit_once('/') {
+        Some((_, name)) if !name.is_empty() => Some(decode_path_segment(name)),
+        Some(_) => None,
+        None => Some(decode_path_segment(href)),
+    }
+}
+
+fn child_text(node: roxmltree::Node, name: &str) -> Option<String> {
+    node.children()
+        .find(|child| child.has_tag_name(("DAV:", name)))
+        .and_then(|child| child.text())
+        .map(|text| text.trim().to_string())
+}
+
+/// Parses a status line like "HTTP/1.1 404 Not Found".
+fn parse_status(status: &str) -> Option<u16> {
+    status.split_whitespace().nth(1)?.parse().ok()
+}
+
+pub(crate) fn encode_path_segment(segment: &str) -> String {
+    segment
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'@' => {
+                (byte as char).to_string()
+            }
+            byte => format!("%{:02X}", byte),
+        })
+        .collect()
+}
+
+pub(crate) fn decode_path_segment(segment: &str) -> String {
+    let bytes = segment.as_bytes();
+    let mut decoded = Vec::new();
+    let mut index = 0;
+
+    while index < bytes.len() {
+        let hex = segment.get(index + 1..index + 3);
+
+        match hex.and_then(|hex| u8::from_str_radix(hex, 16).ok()) {
+            Some(byte) if bytes[index] == b'%' => {
+                decoded.push(byte);
+                index += 3;
+            }
+            _ => {
+                decoded.push(bytes[index]);
+                index += 1;
+            }
+        }
+    }
+
+    String::from_utf8_lossy(&decoded).into_owned()
+}
@@ -0,0 +1,70 @@
+use super::*;
+
+#[test]
+fn parse_multistatus_1() {
+    assert_eq!(
+        parse_multistatus(
+            r#"<?xml version="1.0"?>
+            <d:multistatus xmlns:d="DAV:" xmlns:cs="http://calendarserver.org/ns/">
+                <d:response>
+                    <d:href>/calendars/test/</d:href>
+                    <d:propstat>
+                        <d:prop><cs:getctag>ctag-1</cs:getctag></d:prop>
+                        <d:status>HTTP/1.1 200 OK</d:status>
+                    </d:propstat>
+                    <d:propstat>
+                        <d:prop><d:sync-token/></d:prop>
+                        <d:status>HTTP/1.1 404 Not Found</d:status>
+                    </d:propstat>
+                </d:response>
+                <d:response>
+                    <d:href>/calendars/test/removed.ics</d:href>
+                    <d:status>HTTP/1.1 404 Not Found</d:status>
+                </d:response>
+                <d:sync-token>token-2</d:sync-token>
+            </d:multistatus>"#
+        )
+        .unwrap(),
+        Multistatus {
+            responses: vec![
+                DavResponse {
+                    href: "/calendars/test/".into(),
+                    status: None,
+                    properties: BTreeMap::from([("getctag".into(), "ctag-1".into())])
+                },
+                DavResponse {
+                    href: "/calendars/test/removed.ics".into(),
+                    status: Some(404),
+                    properties: BTreeMap::new()
+                }
+            ],
+            sync_token: Some("token-2".into())
+        }
+    )
+}
+
+#[test]
+fn member_name_1() {
+    assert_eq!(
+        member_name("/calendars/test/test%40uid%20bday.ics"),
+        Some("test@uid bday.ics".into())
+    );
+    assert_eq!(member_name("/calendars/test/"), None);
+    assert_eq!(
+        encode_path_segment("test@uid bday/2023.ics"),
+        "test@uid%20bday%2F2023.ics"
+    );
+}
+
+#[test]
+fn dav_error_1() {
+    assert_eq!(
+        crate::EventExtractorError::from(DavError::UnexpectedStatus(
+            "PUT".into(),
+            "https://example.com/calendars/test/".into(),
+            401
+        ))
+        .to_string(),
+        "WebDAV error: PUT \"https://example.com/calendars/test/\" failed with status 401"
+    )
+}
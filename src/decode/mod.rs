@@ -0,0 +1,198 @@
+#[cfg(test)]
+mod tests;
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use encoding_rs::Encoding;
+use ical::property::Property;
+use thiserror::Error;
+
+use crate::find_param;
+
+#[derive(Error, Debug)]
+pub enum DecodeError {
+    #[error("encoding \"{}\" is not supported", .0)]
+    UnsupportedEncoding(String),
+    #[error("charset \"{}\" is not supported", .0)]
+    UnsupportedCharset(String),
+    #[error("decoding the value of property \"{}\" failed: {}", .0, .1)]
+    DecodingFailed(String, String),
+}
+
+/// Returns a copy of the property with its value decoded according to the
+/// vCard 2.1 "ENCODING" and "CHARSET" parameters.
+///
+/// Both parameters are removed from the returned property, as they no longer
+/// describe its value.
+pub fn decode_property(property: &Property) -> Result<Property, DecodeError> {
+    let encoding = find_param(property, "ENCODING");
+    let charset = find_param(property, "CHARSET");
+
+    let value = match (&property.value, encoding) {
+        (None, _) => None,
+        (Some(value), None) => Some(value.clone()),
+        (Some(value), Some(encoding)) => {
+            let bytes = match encoding.to_uppercase().as_str() {
+                "QUOTED-PRINTABLE" => {
+                    quoted_printable::decode(value, quoted_printable::ParseMode::Robust).map_err(
+                        |e| DecodeError::DecodingFailed(property.name.clone(), e.to_string()),
+                    )?
+                }
+                "BASE64" | "B" => STANDARD.decode(value).map_err(|e| {
+                    DecodeError::DecodingFailed(property.name.clone(), e.to_string())
+                })?,
+                // the value was already converted to UTF-8, see `transcode_vcard`
+                "8BIT" | "7BIT" => return Ok(strip_encoding(property, Some(value.clone()))),
+                _ => return Err(DecodeError::UnsupportedEncoding(encoding.into())),
+            };
+
+            Some(decode_charset(&property.name, &bytes, charset)?)
+        }
+    };
+
+    Ok(strip_encoding(property, value))
+}
+
+/// Converts the lines of a vCard file to UTF-8 according to the "CHARSET"
+/// parameters of their properties and joins the soft line breaks of
+/// quoted-printable values, so that the vCard parser can read them.
+///
+/// Lines which are valid UTF-8 are kept as they are, as files are often
+/// converted to UTF-8 without updating their parameters.
+pub fn transcode_vcard(bytes: &[u8]) -> Result<String, DecodeError> {
+    let mut text = String::with_capacity(bytes.len());
+    let mut lines = bytes.split_inclusive(|&byte| byte == b'\n');
+    let mut header = LineHeader::default();
+
+    while let Some(line) = lines.next() {
+        let (content, mut ending) = split_line_ending(line);
+        let mut content = content.to_vec();
+
+        // continuation lines belong to the property of the previous line
+        if !line.starts_with(b" ") && !line.starts_with(b"\t") {
+            header = LineHeader::parse(&content);
+        }
+
+        while header.quoted_printable && content.ends_with(b"=") {
+            let Some(next) = lines.next() else {
+                break;
+            };
+            let (next_content, next_ending) = split_line_ending(next);
+
+            content.pop();
+            content.extend_from_slice(
+                next_content
+                    .strip_prefix(b" ")
+                    .or(next_content.strip_prefix(b"\t"))
+                    .unwrap_or(next_content),
+            );
+            ending = next_ending;
+        }
+
+        match (std::str::from_utf8(&content), header.charset) {
+            (Ok(content), _) => text.push_str(content),
+            (Err(_), Some(encoding)) => {
+                text.push_str(&decode_bytes(&header.name, &content, encoding)?)
+            }
+            (Err(_), None) => {
+                return Err(DecodeError::DecodingFailed(
+                    header.name,
+                    "invalid UTF-8 sequence".into(),
+                ))
+            }
+        }
+        text.push_str(&String::from_utf8_lossy(ending));
+    }
+
+    Ok(text)
+}
+
+/// Name and encoding parameters of a raw vCard line.
+#[derive(Default)]
+struct LineHeader {
+    name: String,
+    charset: Option<&'static Encoding>,
+    quoted_printable: bool,
+}
+
+impl LineHeader {
+    fn parse(content: &[u8]) -> Self {
+        let mut quoted = false;
+        let end = content
+            .iter()
+            .position(|&byte| {
+                quoted ^= byte == b'"';
+                byte == b':' && !quoted
+            })
+            .unwrap_or(0);
+        let header = String::from_utf8_lossy(&content[..end]);
+        let mut parts = header.split(';');
+        let mut line_header = LineHeader {
+            name: parts.next().unwrap_or_default().to_uppercase(),
+            ..Default::default()
+        };
+
+        for (key, value) in parts.filter_map(|part| part.split_once('=')) {
+            if key.eq_ignore_ascii_case("CHARSET") {
+                line_header.charset = Encoding::for_label(value.trim_matches('"').as_bytes());
+            } else if key.eq_ignore_ascii_case("ENCODING") {
+                line_header.quoted_printable = value.eq_ignore_ascii_case("QUOTED-PRINTABLE");
+            }
+        }
+
+        line_header
+    }
+}
+
+fn split_line_ending(line: &[u8]) -> (&[u8], &[u8]) {
+    let end = line
+        .iter()
+        .rposition(|&byte| byte != b'\r' && byte != b'\n')
+        .map_or(0, |position| position + 1);
+
+    line.split_at(end)
+}
+
+/// Removes the "ENCODING" and "CHARSET" parameters from a copy of the
+/// property with the given value.
+fn strip_encoding(property: &Property, value: Option<String>) -> Property {
+    Property {
+        name: property.name.clone(),
+        params: property.params.as_ref().and_then(|params| {
+            let params: Vec<(String, Vec<String>)> = params
+                .iter()
+                .filter(|(key, _)| key != "ENCODING" && key != "CHARSET")
+                .cloned()
+                .collect();
+
+            (!params.is_empty()).then_some(params)
+        }),
+        value,
+    }
+}
+
+fn decode_charset(name: &str, bytes: &[u8], charset: Option<&str>) -> Result<String, DecodeError> {
+    let encoding = match charset {
+        Some(charset) => Encoding::for_label(charset.as_bytes())
+            .ok_or(DecodeError::UnsupportedCharset(charset.into()))?,
+        None => encoding_rs::UTF_8,
+    };
+
+    decode_bytes(name, bytes, encoding)
+}
+
+fn decode_bytes(
+    name: &str,
+    bytes: &[u8],
+    encoding: &'static Encoding,
+) -> Result<String, DecodeError> {
+    let (text, _, had_errors) = encoding.decode(bytes);
+
+    if had_errors {
+        return Err(DecodeError::DecodingFailed(
+            name.into(),
+            format!("invalid {} sequence", encoding.name()),
+        ));
+    }
+
+    Ok(text.into_owned())
+}
@@ -0,0 +1,154 @@
+use super::*;
+
+#[test]
+fn decode_property_1() {
+    assert_eq!(
+        format!(
+            "{:?}",
+            decode_property(&Property {
+                name: "FN".into(),
+                params: Some(vec![
+                    ("ENCODING".into(), vec!["QUOTED-PRINTABLE".into()]),
+                    ("CHARSET".into(), vec!["UTF-8".into()])
+                ]),
+                value: Some("=C3=9Cmit".into())
+            })
+            .unwrap()
+        ),
+        format!(
+            "{:?}",
+            Property {
+                name: "FN".into(),
+                params: None,
+                value: Some("Ümit".into())
+            }
+        )
+    )
+}
+
+#[test]
+fn decode_property_2() {
+    assert_eq!(
+        decode_property(&Property {
+            name: "FN".into(),
+            params: Some(vec![
+                ("ENCODING".into(), vec!["QUOTED-PRINTABLE".into()]),
+                ("CHARSET".into(), vec!["ISO-8859-1".into()])
+            ]),
+            value: Some("J=FCrgen".into())
+        })
+        .unwrap()
+        .value,
+        Some("Jürgen".into())
+    )
+}
+
+#[test]
+fn decode_property_3() {
+    assert_eq!(
+        decode_property(&Property {
+            name: "FN".into(),
+            params: Some(vec![
+                ("ENCODING".into(), vec!["BASE64".into()]),
+                ("CHARSET".into(), vec!["Windows-1252".into()])
+            ]),
+            value: Some("gCBTb/Bu".into())
+        })
+        .unwrap()
+        .value,
+        Some("€ Soðn".into())
+    )
+}
+
+#[test]
+fn decode_property_4() {
+    assert_eq!(
+        format!(
+            "{:?}",
+            decode_property(&Property {
+                name: "FN".into(),
+                params: Some(vec![("LANGUAGE".into(), vec!["de".into()])]),
+                value: Some("Test Person".into())
+            })
+            .unwrap()
+        ),
+        format!(
+            "{:?}",
+            Property {
+                name: "FN".into(),
+                params: Some(vec![("LANGUAGE".into(), vec!["de".into()])]),
+                value: Some("Test Person".into())
+            }
+        )
+    )
+}
+
+#[test]
+fn decode_property_5() {
+    assert!(decode_property(&Property {
+        name: "FN".into(),
+        params: Some(vec![("ENCODING".into(), vec!["UUENCODE".into()])]),
+        value: Some("value".into())
+    })
+    .is_err())
+}
+
+/// Decoded FN value of the single contact of a vCard file.
+fn decoded_name(bytes: &[u8]) -> String {
+    let text = transcode_vcard(bytes).unwrap();
+    let contact = ical::VcardParser::new(text.as_bytes())
+        .next()
+        .unwrap()
+        .unwrap();
+    let property = contact
+        .properties
+        .iter()
+        .find(|property| property.name == "FN")
+        .unwrap();
+
+    decode_property(property).unwrap().value.unwrap()
+}
+
+#[test]
+fn transcode_vcard_1() {
+    assert_eq!(
+        decoded_name(
+            "BEGIN:VCARD\r\nVERSION:2.1\r\nFN;ENCODING=8BIT;CHARSET=ISO-8859-1:Jürgen\r\nEND:VCARD\r\n"
+                .as_bytes()
+        ),
+        "Jürgen"
+    )
+}
+
+#[test]
+fn transcode_vcard_2() {
+    assert_eq!(
+        decoded_name(b"BEGIN:VCARD\r\nVERSION:2.1\r\nFN;ENCODING=8BIT;CHARSET=ISO-8859-1:J\xFCrgen\r\nEND:VCARD\r\n"),
+        "Jürgen"
+    );
+    assert_eq!(
+        decoded_name(b"BEGIN:VCARD\r\nVERSION:2.1\r\nFN;CHARSET=Windows-1252:\x80 So\xF0n\r\n N\xFCrnberg\r\nEND:VCARD\r\n"),
+        "€ SoðnNürnberg"
+    )
+}
+
+#[test]
+fn transcode_vcard_3() {
+    assert_eq!(
+        decoded_name(
+            b"BEGIN:VCARD\r\nVERSION:2.1\r\nFN;CHARSET=UTF-8;ENCODING=QUOTED-PRINTABLE:J=C3=BCrgen=20=\r\nM=C3=\r\n=BCller=\r\n\r\nBDAY:19901005\r\nEND:VCARD\r\n"
+        ),
+        "Jürgen Müller"
+    );
+    assert_eq!(
+        decoded_name(
+            b"BEGIN:VCARD\r\nVERSION:2.1\r\nFN;CHARSET=UTF-8;ENCODING=QUOTED-PRINTABLE:J=C3=\r\n =BCrgen\r\nEND:VCARD\r\n"
+        ),
+        "Jürgen"
+    )
+}
+
+#[test]
+fn transcode_vcard_4() {
+    assert!(transcode_vcard(b"BEGIN:VCARD\r\nFN:J\xFCrgen\r\nEND:VCARD\r\n").is_err())
+}
@@ -0,0 +1,136 @@
+#[cfg(test)]
+mod tests;
+
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    path::PathBuf,
+};
+
+use chrono::Datelike;
+
+use crate::{
+    config::Entry,
+    filter::normalize_uid,
+    report::{Diagnostic, Report},
+    ExtractedEvent,
+};
+
+/// UID and source of a contact.
+type ContactKey = (String, PathBuf);
+
+/// Month, day and, if known, year of a birthday.
+type Birthday = (u32, u32, Option<i32>);
+
+/// Removes the events of duplicate contacts.
+///
+/// Contacts are duplicates if they have the same UID or the same name
+/// (ignoring case and whitespace) and birthday. Of every set of duplicates
+/// only the events of the contact with the smallest UID and source are kept,
+/// so the UIDs of the events do not depend on the order of the input.
+///
+/// Returns the remaining events and a diagnostic for every merged contact.
+pub fn deduplicate(events: Vec<ExtractedEvent>) -> (Vec<ExtractedEvent>, Vec<Diagnostic>) {
+    let mut contacts: BTreeMap<ContactKey, (String, Birthday)> = BTreeMap::new();
+
+    for event in &events {
+        contacts
+            .entry((event.contact_uid.clone(), event.source.clone()))
+            .or_insert_with(|| {
+                (
+                    normalize_name(&event.name),
+                    (
+                        event.date.month(),
+                        event.date.day(),
+                        event.age.map(|age| event.date.year() - age),
+                    ),
+                )
+            });
+    }
+
+    let keys: Vec<&ContactKey> = contacts.keys().collect();
+    let mut parents: Vec<usize> = (0..keys.len()).collect();
+    let mut by_uid = HashMap::new();
+    let mut by_name = HashMap::new();
+
+    for (index, (key, (name, birthday))) in contacts.iter().enumerate() {
+        for first in [
+            *by_uid.entry(normalize_uid(&key.0)).or_insert(index),
+            *by_name.entry((name.clone(), *birthday)).or_insert(index),
+        ] {
+            union(&mut parents, first, index);
+        }
+    }
+
+    let mut diagnostics = Vec::new();
+    let mut kept = BTreeSet::new();
+
+    for (index, key) in keys.iter().enumerate() {
+        let root = find(&mut parents, index);
+
+        if root == index {
+            kept.insert((*key).clone());
+        } else {
+            diagnostics.push(Diagnostic::MergedContact {
+                source: key.1.clone(),
+                uid: key.0.clone(),
+                kept_source: keys[root].1.clone(),
+                kept_uid: keys[root].0.clone(),
+            });
+        }
+    }
+
+    let mut uids = BTreeSet::new();
+    let events = events
+        .into_iter()
+        .filter(|event| kept.contains(&(event.contact_uid.clone(), event.source.clone())))
+        .filter(|event| uids.insert(event.uid.clone()))
+        .collect();
+
+    (events, diagnostics)
+}
+
+/// Removes the events of duplicate contacts if the configuration entry is
+/// deduplicated, adding a diagnostic for every merged contact to the report.
+pub fn deduplicate_entry(
+    config_entry: &Entry,
+    events: Vec<ExtractedEvent>,
+    report: &mut Report,
+) -> Vec<ExtractedEvent> {
+    if !config_entry.deduplicate {
+        return events;
+    }
+
+    let (events, diagnostics) = deduplicate(events);
+
+    for diagnostic in &diagnostics {
+        log::info!("{}", diagnostic);
+    }
+    report.diagnostics.extend(diagnostics);
+
+    events
+}
+
+fn normalize_name(name: &str) -> String {
+    name.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+fn find(parents: &mut [usize], index: usize) -> usize {
+    let mut root = index;
+
+    while parents[root] != root {
+        root = parents[root];
+    }
+    parents[index] = root;
+
+    root
+}
+
+/// Joins the sets of both contacts; the smaller index becomes the root.
+fn union(parents: &mut [usize], a: usize, b: usize) {
+    let (a, b) = (find(parents, a), find(parents, b));
+
+    parents[a.max(b)] = a.min(b);
+}
@@ -0,0 +1,132 @@
+use std::{fs, path::Path};
+
+use crate::{
+    config::Entry,
+    extract_entry, extract_merged, merge_entries, process_merged,
+    testing::{vcard, vcard_with_birthday},
+};
+
+use super::*;
+
+fn entry(input: &Path, output: &Path) -> Entry {
+    Entry {
+        input: input.to_string_lossy().into(),
+        output: output.to_string_lossy().into(),
+        deduplicate: true,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn deduplicate_1() {
+    let input = tempfile::tempdir().unwrap();
+
+    fs::write(
+        input.path().join("home.vcf"),
+        vcard_with_birthday("jane", "Jane Doe", "19901005")
+            + &vcard_with_birthday("john", "John Doe", "19851224"),
+    )
+    .unwrap();
+    fs::write(
+        input.path().join("work.vcf"),
+        vcard_with_birthday("jane-work", "jane  DOE", "19901005")
+            + &vcard_with_birthday("urn:uuid:john", "John D.", "19851224")
+            + &vcard_with_birthday("max", "Jane Doe", "19911005"),
+    )
+    .unwrap();
+
+    let (events, _) = extract_entry(&entry(input.path(), input.path()), &[2023]).unwrap();
+    let (events, diagnostics) = deduplicate(events);
+
+    assert_eq!(
+        events
+            .iter()
+            .map(|event| event.uid.as_str())
+            .collect::<Vec<_>>(),
+        vec!["jane_bday_2023", "max_bday_2023", "john_bday_2023"]
+    );
+    assert_eq!(
+        diagnostics,
+        vec![
+            Diagnostic::MergedContact {
+                source: input.path().join("work.vcf"),
+                uid: "jane-work".into(),
+                kept_source: input.path().join("home.vcf"),
+                kept_uid: "jane".into(),
+            },
+            Diagnostic::MergedContact {
+                source: input.path().join("work.vcf"),
+                uid: "urn:uuid:john".into(),
+                kept_source: input.path().join("home.vcf"),
+                kept_uid: "john".into(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn process_merged_1() {
+    let home = tempfile::tempdir().unwrap();
+    let work = tempfile::tempdir().unwrap();
+    let output = tempfile::tempdir().unwrap();
+    let entries = vec![
+        entry(home.path(), output.path()),
+        entry(work.path(), output.path()),
+        Entry {
+            deduplicate: false,
+            ..entry(work.path(), output.path())
+        },
+    ];
+
+    fs::write(
+        home.path().join("b.vcf"),
+        vcard_with_birthday("b", "Jane Doe", "19901005"),
+    )
+    .unwrap();
+    fs::write(
+        work.path().join("a.vcf"),
+        vcard_with_birthday("a", "Jane Doe", "19901005"),
+    )
+    .unwrap();
+
+    let merged = merge_entries(&entries);
+    assert_eq!(merged.len(), 2);
+    assert_eq!(merged[0].len(), 2);
+
+    let report = process_merged(&merged[0]).unwrap();
+    assert_eq!(report.contacts, 2);
+    assert_eq!(report.events, 4);
+    assert_eq!(report.merged_contacts(), 1);
+
+    let mut files: Vec<String> = fs::read_dir(output.path())
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+        .filter(|name| name.ends_with(".ics"))
+        .collect();
+    files.sort();
+    assert_eq!(files.len(), 4);
+    assert!(files.iter().all(|name| name.starts_with("a_bday_")));
+}
+
+#[test]
+fn extract_merged_1() {
+    let home = tempfile::tempdir().unwrap();
+    let work = tempfile::tempdir().unwrap();
+    let entries = [
+        entry(home.path(), home.path()),
+        entry(work.path(), home.path()),
+    ];
+
+    fs::write(home.path().join("b.vcf"), vcard("b", "Jane Doe")).unwrap();
+    fs::write(work.path().join("a.vcf"), vcard("a", "Jane Doe")).unwrap();
+
+    let (events, report) = extract_merged(&merge_entries(&entries)[0], &[2023]).unwrap();
+    assert_eq!(
+        events
+            .iter()
+            .map(|event| event.uid.as_str())
+            .collect::<Vec<_>>(),
+        vec!["a_bday_2023"]
+    );
+    assert_eq!(report.merged_contacts(), 1);
+}
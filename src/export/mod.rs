@@ -0,0 +1,67 @@
+#[cfg(test)]
+mod tests;
+
+use std::io::Write;
+
+use serde::Serialize;
+use thiserror::Error;
+
+use crate::{EventKind, ExtractedEvent};
+
+#[derive(Error, Debug)]
+pub enum ExportError {
+    #[error("csv error")]
+    CsvError(#[from] csv::Error),
+    #[error("serde_json error")]
+    SerdeJsonError(#[from] serde_json::Error),
+    #[error("std::io error")]
+    StdIoError(#[from] std::io::Error),
+}
+
+/// Flat representation of an event.
+#[derive(Serialize, Debug, PartialEq)]
+struct Row<'a> {
+    uid: &'a str,
+    name: &'a str,
+    kind: EventKind,
+    date: String,
+    age: Option<i32>,
+    source: String,
+}
+
+impl<'a> From<&'a ExtractedEvent> for Row<'a> {
+    fn from(event: &'a ExtractedEvent) -> Self {
+        Row {
+            uid: &event.contact_uid,
+            name: &event.name,
+            kind: event.kind,
+            date: event.date.format("%Y-%m-%d").to_string(),
+            age: event.age,
+            source: event.source.to_string_lossy().into(),
+        }
+    }
+}
+
+/// Writes the events as CSV with a header row.
+pub fn write_csv(events: &[ExtractedEvent], writer: &mut dyn Write) -> Result<(), ExportError> {
+    let mut writer = csv::Writer::from_writer(writer);
+
+    for event in events {
+        writer.serialize(Row::from(event))?;
+    }
+
+    Ok(writer.flush()?)
+}
+
+/// Writes the events as JSON Lines, i.e. one JSON object per line.
+pub fn write_json_lines(
+    events: &[ExtractedEvent],
+    writer: &mut dyn Write,
+) -> Result<(), ExportError> {
+    for event in events {
+        serde_json::to_writer(&mut *writer, &Row::from(event))?;
+        writer.write_all(b"\n")?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,56 @@
+use chrono::NaiveDate;
+use ical::parser::ical::component::IcalEvent;
+
+use super::*;
+
+fn test_events() -> Vec<ExtractedEvent> {
+    vec![
+        ExtractedEvent {
+            uid: "test_uid_bday_2000".into(),
+            contact_uid: "test_uid".into(),
+            name: "Doe, J.".into(),
+            kind: EventKind::Birthday,
+            date: NaiveDate::from_ymd_opt(2000, 10, 5).unwrap(),
+            age: Some(10),
+            source: "/path/input/test.vcf".into(),
+            event: IcalEvent::new(),
+        },
+        ExtractedEvent {
+            uid: "other_uid_bday_2000".into(),
+            contact_uid: "other_uid".into(),
+            name: "Test Person".into(),
+            kind: EventKind::Birthday,
+            date: NaiveDate::from_ymd_opt(2000, 12, 24).unwrap(),
+            age: None,
+            source: "/path/input/test.vcf".into(),
+            event: IcalEvent::new(),
+        },
+    ]
+}
+
+#[test]
+fn write_csv_1() {
+    let mut out = Vec::new();
+
+    write_csv(&test_events(), &mut out).unwrap();
+
+    assert_eq!(
+        String::from_utf8(out).unwrap(),
+        "uid,name,kind,date,age,source\n\
+         test_uid,\"Doe, J.\",birthday,2000-10-05,10,/path/input/test.vcf\n\
+         other_uid,Test Person,birthday,2000-12-24,,/path/input/test.vcf\n"
+    )
+}
+
+#[test]
+fn write_json_lines_1() {
+    let mut out = Vec::new();
+
+    write_json_lines(&test_events(), &mut out).unwrap();
+
+    assert_eq!(
+        String::from_utf8(out).unwrap(),
+        "{\"uid\":\"test_uid\",\"name\":\"Doe, J.\",\"kind\":\"birthday\",\"date\":\"2000-10-05\",\"age\":10,\"source\":\"/path/input/test.vcf\"}\n\
+         {\"uid\":\"other_uid\",\"name\":\"Test Person\",\"kind\":\"birthday\",\"date\":\"2000-12-24\",\"age\":null,\"source\":\"/path/input/test.vcf\"}\n"
+    )
+}
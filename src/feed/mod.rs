@@ -0,0 +1,79 @@
+#[cfg(test)]
+mod tests;
+
+use chrono::{Duration, NaiveDate};
+
+use crate::{
+    ser::{escape_xml, unescape_text},
+    upcoming::upcoming_events,
+    ExtractedEvent,
+};
+
+/// Renders an Atom (RFC 4287) feed with one entry per event from `today` up
+/// to the given number of days ahead.
+///
+/// Entry identifiers are derived from the event identifiers. The update
+/// time of an entry is the day it entered the look-ahead window, so that it
+/// does not change between runs.
+pub fn render_feed(events: &[ExtractedEvent], today: NaiveDate, days: i64, title: &str) -> String {
+    let upcoming = upcoming_events(events, today, days);
+    let updated = |event: &ExtractedEvent| format_time(event.date - Duration::days(days));
+    let mut feed = format!(
+        "<?xml version=\"1.0\" encoding=\"utf-8\"?>\n\
+         <feed xmlns=\"http://www.w3.org/2005/Atom\">\n\
+         <id>urn:event-extractor:feed:{}</id>\n\
+         <title>{}</title>\n\
+         <author><name>event-extractor</name></author>\n\
+         <updated>{}</updated>\n",
+        encode_urn(title),
+        escape_xml(title),
+        upcoming
+            .iter()
+            .map(|event| updated(event))
+            .max()
+            .unwrap_or_else(|| format_time(today))
+    );
+
+    for event in upcoming {
+        let summary = event
+            .event
+            .properties
+            .iter()
+            .find(|property| property.name == "SUMMARY")
+            .and_then(|property| property.value.as_deref())
+            .map(unescape_text)
+            .unwrap_or_else(|| event.name.clone());
+
+        feed += &format!(
+            "<entry>\n\
+             <id>urn:event-extractor:event:{}</id>\n\
+             <title>{}</title>\n\
+             <updated>{}</updated>\n\
+             <summary>{}</summary>\n\
+             </entry>\n",
+            encode_urn(&event.uid),
+            escape_xml(&summary),
+            updated(event),
+            event.date.format("%A, %Y-%m-%d")
+        );
+    }
+
+    feed + "</feed>\n"
+}
+
+fn format_time(date: NaiveDate) -> String {
+    date.format("%Y-%m-%dT00:00:00Z").to_string()
+}
+
+/// Percent-encodes all characters not allowed in a URN.
+fn encode_urn(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (byte as char).to_string()
+            }
+            byte => format!("%{:02X}", byte),
+        })
+        .collect()
+}
@@ -0,0 +1,48 @@
+use super::*;
+use crate::testing;
+
+fn event(uid: &str, name: &str, date: NaiveDate) -> ExtractedEvent {
+    ExtractedEvent {
+        uid: uid.into(),
+        ..testing::event(name, date, None)
+    }
+}
+
+#[test]
+fn render_feed_1() {
+    let today = NaiveDate::from_ymd_opt(2023, 12, 20).unwrap();
+    let events = [
+        event(
+            "past_bday_2023",
+            "Past",
+            NaiveDate::from_ymd_opt(2023, 12, 19).unwrap(),
+        ),
+        event(
+            "test/uid_bday_2023",
+            "Test & Person",
+            NaiveDate::from_ymd_opt(2023, 12, 24).unwrap(),
+        ),
+        event(
+            "late_bday_2024",
+            "Late",
+            NaiveDate::from_ymd_opt(2024, 2, 1).unwrap(),
+        ),
+    ];
+
+    assert_eq!(
+        render_feed(&events, today, 14, "Birthdays"),
+        "<?xml version=\"1.0\" encoding=\"utf-8\"?>\n\
+         <feed xmlns=\"http://www.w3.org/2005/Atom\">\n\
+         <id>urn:event-extractor:feed:Birthdays</id>\n\
+         <title>Birthdays</title>\n\
+         <author><name>event-extractor</name></author>\n\
+         <updated>2023-12-10T00:00:00Z</updated>\n\
+         <entry>\n\
+         <id>urn:event-extractor:event:test%2Fuid_bday_2023</id>\n\
+         <title>Birthday: Test &amp; Person</title>\n\
+         <updated>2023-12-10T00:00:00Z</updated>\n\
+         <summary>Sunday, 2023-12-24</summary>\n\
+         </entry>\n\
+         </feed>\n"
+    )
+}
@@ -0,0 +1,250 @@
+#[cfg(test)]
+mod tests;
+
+use std::collections::BTreeSet;
+
+use ical::parser::vcard::component::VcardContact;
+use regex::Regex;
+use thiserror::Error;
+
+use crate::{
+    config::{Entry, PropertyPattern},
+    decode::decode_property,
+    ser::unescape_text,
+    EventExtractorError, ExtractedEvent,
+};
+
+/// Properties the filters are applied to, besides those of the property
+/// patterns.
+pub const FILTERED_PROPERTIES: [&str; 5] = [
+    "CATEGORIES",
+    "KIND",
+    "X-ADDRESSBOOKSERVER-KIND",
+    "MEMBER",
+    "X-ADDRESSBOOKSERVER-MEMBER",
+];
+
+#[derive(Error, Debug)]
+pub enum FilterError {
+    #[error("invalid regular expression \"{}\"", .0)]
+    InvalidRegex(String, #[source] regex::Error),
+}
+
+/// Contact of the kind "group".
+#[derive(Debug, PartialEq, Clone)]
+pub struct Group {
+    pub uid: String,
+    pub name: String,
+    /// UIDs of the members.
+    pub members: BTreeSet<String>,
+}
+
+/// Filters of a configuration entry, ready to be applied to contacts.
+#[derive(Debug, Default)]
+pub struct ContactFilter {
+    include_categories: Vec<String>,
+    exclude_categories: Vec<String>,
+    exclude_kinds: Vec<String>,
+    include_groups: Vec<String>,
+    exclude_groups: Vec<String>,
+    include_properties: Vec<(String, Regex)>,
+    exclude_properties: Vec<(String, Regex)>,
+}
+
+impl ContactFilter {
+    /// Creates the filter of a configuration entry.
+    pub fn new(config_entry: &Entry) -> Result<Self, EventExtractorError> {
+        let options = &config_entry.filter;
+
+        Ok(ContactFilter {
+            include_categories: lowercase(&options.include_categories),
+            exclude_categories: lowercase(&options.exclude_categories),
+            exclude_kinds: lowercase(&options.exclude_kinds),
+            include_groups: options.include_groups.clone(),
+            exclude_groups: options.exclude_groups.clone(),
+            include_properties: compile(&options.include_properties)?,
+            exclude_properties: compile(&options.exclude_properties)?,
+        })
+    }
+
+    /// Checks whether the events of a contact are generated.
+    ///
+    /// The group filters cannot be applied to a single contact; they are
+    /// applied to the events by `filter_groups`.
+    pub fn matches(&self, contact: &VcardContact) -> bool {
+        let categories = list_values(contact, &["CATEGORIES"]);
+        let kind = list_values(contact, &["KIND", "X-ADDRESSBOOKSERVER-KIND"])
+            .into_iter()
+            .next()
+            .unwrap_or_else(|| "individual".into());
+
+        (self.include_categories.is_empty()
+            || categories
+                .iter()
+                .any(|category| self.include_categories.contains(category)))
+            && !categories
+                .iter()
+                .any(|category| self.exclude_categories.contains(category))
+            && !self.exclude_kinds.contains(&kind)
+            && (self.include_properties.is_empty()
+                || self
+                    .include_properties
+                    .iter()
+                    .any(|(name, regex)| matches_property(contact, name, regex)))
+            && !self
+                .exclude_properties
+                .iter()
+                .any(|(name, regex)| matches_property(contact, name, regex))
+    }
+
+    /// Removes the events of the contacts excluded by the group filters,
+    /// resolving the groups among the given ones.
+    pub fn filter_groups(
+        &self,
+        events: Vec<ExtractedEvent>,
+        groups: &[Group],
+    ) -> Vec<ExtractedEvent> {
+        if self.include_groups.is_empty() && self.exclude_groups.is_empty() {
+            return events;
+        }
+
+        let include_members = match self.include_groups.is_empty() {
+            true => None,
+            false => Some(members(groups, &self.include_groups)),
+        };
+        let exclude_members = members(groups, &self.exclude_groups);
+
+        events
+            .into_iter()
+            .filter(|event| {
+                let uid = normalize_uid(&event.contact_uid);
+
+                include_members
+                    .as_ref()
+                    .is_none_or(|members| members.contains(&uid))
+                    && !exclude_members.contains(&uid)
+            })
+            .collect()
+    }
+}
+
+impl Group {
+    /// Checks whether the group has the given UID or name.
+    pub fn is(&self, reference: &str) -> bool {
+        normalize_uid(reference) == self.uid || reference == self.name
+    }
+
+    /// Reads a group from a contact of the kind "group".
+    pub fn from_contact(contact: &VcardContact) -> Option<Group> {
+        let kind = list_values(contact, &["KIND", "X-ADDRESSBOOKSERVER-KIND"]);
+
+        if kind.first().map(String::as_str) != Some("group") {
+            return None;
+        }
+
+        Some(Group {
+            uid: normalize_uid(&contact_uid(contact)?),
+            name: decoded_values(contact, "FN")
+                .into_iter()
+                .next()
+                .map(|name| unescape_text(&name))
+                .unwrap_or_default(),
+            members: ["MEMBER", "X-ADDRESSBOOKSERVER-MEMBER"]
+                .iter()
+                .flat_map(|name| decoded_values(contact, name))
+                .map(|member| normalize_uid(&member))
+                .collect(),
+        })
+    }
+}
+
+/// UIDs of the members of the given groups.
+fn members(groups: &[Group], selected: &[String]) -> BTreeSet<String> {
+    groups
+        .iter()
+        .filter(|group| selected.iter().any(|elem| group.is(elem)))
+        .flat_map(|group| group.members.iter().cloned())
+        .collect()
+}
+
+fn compile(patterns: &[PropertyPattern]) -> Result<Vec<(String, Regex)>, FilterError> {
+    patterns
+        .iter()
+        .map(|pattern| {
+            let regex = Regex::new(&pattern.regex)
+                .map_err(|e| FilterError::InvalidRegex(pattern.regex.clone(), e))?;
+
+            Ok((pattern.property.to_uppercase(), regex))
+        })
+        .collect()
+}
+
+fn matches_property(contact: &VcardContact, name: &str, regex: &Regex) -> bool {
+    decoded_values(contact, name)
+        .iter()
+        .any(|value| regex.is_match(&unescape_text(value)))
+}
+
+/// Decoded values of all properties with the given name.
+fn decoded_values(contact: &VcardContact, name: &str) -> Vec<String> {
+    contact
+        .properties
+        .iter()
+        .filter(|property| property.name == name)
+        .filter_map(|property| decode_property(property).ok()?.value)
+        .collect()
+}
+
+/// Lowercase values of comma separated list properties.
+fn list_values(contact: &VcardContact, names: &[&str]) -> Vec<String> {
+    names
+        .iter()
+        .flat_map(|name| decoded_values(contact, name))
+        .flat_map(|value| split_list(&value))
+        .map(|value| value.trim().to_lowercase())
+        .filter(|value| !value.is_empty())
+        .collect()
+}
+
+/// Splits a list value at the commas which are not escaped.
+fn split_list(value: &str) -> Vec<String> {
+    let mut values = Vec::new();
+    let mut start = 0;
+    let mut escaped = false;
+
+    for (index, character) in value.char_indices() {
+        match character {
+            ',' if !escaped => {
+                values.push(unescape_text(&value[start..index]));
+                start = index + 1;
+            }
+            _ => {}
+        }
+        escaped = character == '\\' && !escaped;
+    }
+    values.push(unescape_text(&value[start..]));
+
+    values
+}
+
+fn contact_uid(contact: &VcardContact) -> Option<String> {
+    contact
+        .properties
+        .iter()
+        .find(|property| property.name == "UID")
+        .and_then(|property| decode_property(property).ok()?.value)
+}
+
+/// Removes the "urn:uuid:" prefix used by `MEMBER` references.
+pub(crate) fn normalize_uid(uid: &str) -> String {
+    let trimmed = uid.trim();
+
+    match trimmed.get(..9) {
+        Some(prefix) if prefix.eq_ignore_ascii_case("urn:uuid:") => trimmed[9..].to_string(),
+        _ => trimmed.to_string(),
+    }
+}
+
+fn lowercase(values: &[String]) -> Vec<String> {
+    values.iter().map(|value| value.to_lowercase()).collect()
+}
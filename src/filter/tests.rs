@@ -0,0 +1,157 @@
+use chrono::NaiveDate;
+use ical::property::Property;
+
+use crate::{config::FilterOptions, testing::event};
+
+use super::*;
+
+fn contact(properties: &[(&str, &str)]) -> VcardContact {
+    VcardContact {
+        properties: properties
+            .iter()
+            .map(|(name, value)| Property {
+                name: name.to_string(),
+                params: None,
+                value: Some(value.to_string()),
+            })
+            .collect(),
+    }
+}
+
+fn filter(options: FilterOptions) -> ContactFilter {
+    ContactFilter::new(&Entry {
+        filter: options,
+        ..Default::default()
+    })
+    .unwrap()
+}
+
+#[test]
+fn matches_1() {
+    let filter = filter(FilterOptions {
+        include_categories: vec!["Family".into(), "Friends".into()],
+        exclude_categories: vec!["vendor".into()],
+        ..Default::default()
+    });
+
+    assert!(filter.matches(&contact(&[("CATEGORIES", "friends,Work")])));
+    assert!(filter.matches(&contact(&[
+        ("CATEGORIES", "Work"),
+        ("CATEGORIES", "Family")
+    ])));
+    assert!(!filter.matches(&contact(&[("CATEGORIES", "Work")])));
+    assert!(!filter.matches(&contact(&[("CATEGORIES", "Family,Vendor")])));
+    assert!(!filter.matches(&contact(&[("CATEGORIES", "Family\\,Vendor")])));
+    assert!(!filter.matches(&contact(&[])));
+}
+
+#[test]
+fn matches_2() {
+    let filter = filter(FilterOptions::default());
+
+    assert!(filter.matches(&contact(&[])));
+    assert!(filter.matches(&contact(&[("KIND", "individual")])));
+    assert!(!filter.matches(&contact(&[("KIND", "org")])));
+    assert!(!filter.matches(&contact(&[("KIND", "Group")])));
+    assert!(!filter.matches(&contact(&[("X-ADDRESSBOOKSERVER-KIND", "group")])));
+}
+
+#[test]
+fn matches_3() {
+    let filter = filter(FilterOptions {
+        include_properties: vec![PropertyPattern {
+            property: "email".into(),
+            regex: "@example\\.com$".into(),
+        }],
+        exclude_properties: vec![PropertyPattern {
+            property: "ORG".into(),
+            regex: "(?i)supplier".into(),
+        }],
+        ..Default::default()
+    });
+
+    assert!(filter.matches(&contact(&[("EMAIL", "jane@example.com")])));
+    assert!(!filter.matches(&contact(&[("EMAIL", "jane@example.org")])));
+    assert!(!filter.matches(&contact(&[
+        ("EMAIL", "jane@example.com"),
+        ("ORG", "Office Supplier Ltd.")
+    ])));
+}
+
+#[test]
+fn filter_groups_1() {
+    let filter = filter(FilterOptions {
+        include_groups: vec!["urn:uuid:team".into()],
+        exclude_groups: vec!["Managers".into()],
+        ..Default::default()
+    });
+    let groups = [
+        Group {
+            uid: "team".into(),
+            name: "Team".into(),
+            members: BTreeSet::from(["a".into(), "b".into()]),
+        },
+        Group {
+            uid: "managers".into(),
+            name: "Managers".into(),
+            members: BTreeSet::from(["b".into()]),
+        },
+    ];
+    let date = NaiveDate::from_ymd_opt(2023, 10, 5).unwrap();
+    let events = vec![
+        event("urn:uuid:a", date, None),
+        event("a", date, None),
+        event("b", date, None),
+        event("c", date, None),
+    ];
+
+    assert_eq!(
+        filter
+            .filter_groups(events, &groups)
+            .iter()
+            .map(|event| event.contact_uid.as_str())
+            .collect::<Vec<_>>(),
+        vec!["urn:uuid:a", "a"]
+    );
+}
+
+#[test]
+fn new_1() {
+    let entry = Entry {
+        filter: FilterOptions {
+            exclude_properties: vec![PropertyPattern {
+                property: "ORG".into(),
+                regex: "(".into(),
+            }],
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert!(matches!(
+        ContactFilter::new(&entry),
+        Err(EventExtractorError::FilterError(FilterError::InvalidRegex(regex, _))) if regex == "("
+    ));
+}
+
+#[test]
+fn group_from_contact_1() {
+    assert_eq!(
+        Group::from_contact(&contact(&[
+            ("KIND", "group"),
+            ("UID", "urn:uuid:team"),
+            ("FN", "Team\\, Berlin"),
+            ("MEMBER", "urn:uuid:a"),
+            ("MEMBER", "URN:UUID:b"),
+        ])),
+        Some(Group {
+            uid: "team".into(),
+            name: "Team, Berlin".into(),
+            members: BTreeSet::from(["a".into(), "b".into()]),
+        })
+    );
+    assert_eq!(
+        Group::from_contact(&contact(&[("UID", "a"), ("FN", "A")])),
+        None
+    );
+}
@@ -0,0 +1,83 @@
+#[cfg(test)]
+mod tests;
+
+use std::path::PathBuf;
+
+use crate::{
+    config::Entry,
+    filter::{normalize_uid, Group},
+    lock::lock_output,
+    report::{Diagnostic, Report},
+    write_events, EventExtractorError, ExtractedEvent,
+};
+
+/// Events of the members of a group.
+pub fn group_events(group: &Group, events: &[ExtractedEvent]) -> Vec<ExtractedEvent> {
+    events
+        .iter()
+        .filter(|event| group.members.contains(&normalize_uid(&event.contact_uid)))
+        .cloned()
+        .collect()
+}
+
+/// Copy of a group whose members include the contacts that members were
+/// merged into when the events were deduplicated.
+fn with_merged_members(group: &Group, diagnostics: &[Diagnostic]) -> Group {
+    let mut group = group.clone();
+
+    for diagnostic in diagnostics {
+        if let Diagnostic::MergedContact { uid, kept_uid, .. } = diagnostic {
+            if group.members.contains(&normalize_uid(uid)) {
+                group.members.insert(normalize_uid(kept_uid));
+            }
+        }
+    }
+
+    group
+}
+
+/// Writes the events of the members of the groups configured by the
+/// `group_outputs` of an entry, each into an output of its own.
+///
+/// The groups are resolved among the given ones, i.e. across all input files
+/// of the entry. Members which were merged into a duplicate (see the
+/// diagnostics of the report) are represented by the contact that was kept.
+pub fn write_group_outputs(
+    config_entry: &Entry,
+    events: &[ExtractedEvent],
+    groups: &[Group],
+    report: &mut Report,
+) -> Result<(), EventExtractorError> {
+    for group_output in &config_entry.group_outputs {
+        let Some(group) = groups.iter().find(|group| group.is(&group_output.group)) else {
+            log::warn!("group \"{}\" was not found", group_output.group);
+            continue;
+        };
+        let group_entry = Entry {
+            name: None,
+            output: group_output.output.clone(),
+            group_outputs: Vec::new(),
+            ..config_entry.clone()
+        };
+        let Some(_lock) = lock_output(&group_entry)? else {
+            let diagnostic = Diagnostic::OutputLocked {
+                path: PathBuf::from(&group_entry.output),
+            };
+
+            log::warn!("{}", diagnostic);
+            report.diagnostics.push(diagnostic);
+            continue;
+        };
+        let events = group_events(&with_merged_members(group, &report.diagnostics), events);
+
+        write_events(&group_entry, &events, report)?;
+        log::info!(
+            "wrote {} events of group \"{}\" to \"{}\"",
+            events.len(),
+            group.name,
+            group_entry.output
+        );
+    }
+
+    Ok(())
+}
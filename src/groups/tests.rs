@@ -0,0 +1,119 @@
+use std::{collections::BTreeSet, fs};
+
+use crate::{
+    config::GroupOutput,
+    process_entry,
+    testing::{output_files, vcard},
+};
+
+use super::*;
+
+#[test]
+fn write_group_outputs_1() {
+    let input = tempfile::tempdir().unwrap();
+    let output = tempfile::tempdir().unwrap();
+    let family = tempfile::tempdir().unwrap();
+    let team = tempfile::tempdir().unwrap();
+
+    fs::write(input.path().join("a.vcf"), vcard("urn:uuid:a", "A")).unwrap();
+    fs::write(input.path().join("b.vcf"), vcard("urn:uuid:b", "B")).unwrap();
+    fs::write(input.path().join("c.vcf"), vcard("urn:uuid:c", "C")).unwrap();
+    fs::write(
+        input.path().join("groups.vcf"),
+        "BEGIN:VCARD\r\nVERSION:4.0\r\nKIND:group\r\nUID:urn:uuid:family\r\nFN:Family\r\nMEMBER:urn:uuid:a\r\nMEMBER:urn:uuid:b\r\nEND:VCARD\r\n\
+         BEGIN:VCARD\r\nVERSION:4.0\r\nKIND:group\r\nUID:urn:uuid:team-a\r\nFN:Team A\r\nMEMBER:urn:uuid:c\r\nEND:VCARD\r\n",
+    )
+    .unwrap();
+
+    let entry = Entry {
+        input: input.path().to_string_lossy().into(),
+        output: output.path().to_string_lossy().into(),
+        group_outputs: vec![
+            GroupOutput {
+                group: "Family".into(),
+                output: family.path().to_string_lossy().into(),
+            },
+            GroupOutput {
+                group: "urn:uuid:team-a".into(),
+                output: team.path().to_string_lossy().into(),
+            },
+            GroupOutput {
+                group: "Unknown".into(),
+                output: team.path().to_string_lossy().into(),
+            },
+        ],
+        ..Default::default()
+    };
+
+    process_entry(&entry).unwrap();
+
+    assert_eq!(
+        output_files(output.path()),
+        vec!["urn:uuid:a", "urn:uuid:b", "urn:uuid:c"]
+    );
+    assert_eq!(
+        output_files(family.path()),
+        vec!["urn:uuid:a", "urn:uuid:b"]
+    );
+    assert_eq!(output_files(team.path()), vec!["urn:uuid:c"]);
+}
+
+#[test]
+fn group_events_1() {
+    let input = tempfile::tempdir().unwrap();
+    let entry = Entry {
+        input: input.path().to_string_lossy().into(),
+        ..Default::default()
+    };
+
+    fs::write(input.path().join("a.vcf"), vcard("urn:uuid:a", "A")).unwrap();
+    fs::write(input.path().join("b.vcf"), vcard("urn:uuid:b", "B")).unwrap();
+
+    let (events, _) = crate::extract_entry(&entry, &[2023]).unwrap();
+    let group = Group {
+        uid: "family".into(),
+        name: "Family".into(),
+        members: BTreeSet::from(["b".into()]),
+    };
+
+    assert_eq!(
+        group_events(&group, &events)
+            .iter()
+            .map(|event| event.name.as_str())
+            .collect::<Vec<_>>(),
+        vec!["B"]
+    );
+}
+
+#[test]
+fn write_group_outputs_2() {
+    let input = tempfile::tempdir().unwrap();
+    let output = tempfile::tempdir().unwrap();
+    let family = tempfile::tempdir().unwrap();
+
+    // the member is merged into its duplicate
+    fs::write(input.path().join("a.vcf"), vcard("urn:uuid:a", "Jane Doe")).unwrap();
+    fs::write(input.path().join("b.vcf"), vcard("urn:uuid:b", "Jane Doe")).unwrap();
+    fs::write(
+        input.path().join("groups.vcf"),
+        "BEGIN:VCARD\r\nVERSION:4.0\r\nKIND:group\r\nUID:urn:uuid:family\r\nFN:Family\r\nMEMBER:urn:uuid:b\r\nEND:VCARD\r\n",
+    )
+    .unwrap();
+
+    let entry = Entry {
+        input: input.path().to_string_lossy().into(),
+        output: output.path().to_string_lossy().into(),
+        deduplicate: true,
+        group_outputs: vec![GroupOutput {
+            group: "Family".into(),
+            output: family.path().to_string_lossy().into(),
+        }],
+        ..Default::default()
+    };
+
+    let report = process_entry(&entry).unwrap();
+
+    assert_eq!(report.merged_contacts(), 1);
+    assert_eq!(output_files(output.path()), vec!["urn:uuid:a"]);
+    assert_eq!(output_files(family.path()), vec!["urn:uuid:a"]);
+}
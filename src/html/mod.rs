@@ -0,0 +1,86 @@
+#[cfg(test)]
+mod tests;
+
+use chrono::{Datelike, Months, NaiveDate};
+
+use crate::{ser::escape_xml, ExtractedEvent};
+
+/// Template used if no custom template is configured.
+///
+/// Templates can use the placeholders "{{title}}", "{{style}}", "{{agenda}}"
+/// and "{{today}}".
+pub const DEFAULT_TEMPLATE: &str = r#"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<meta name="viewport" content="width=device-width, initial-scale=1">
+<title>{{title}}</title>
+<style>{{style}}</style>
+</head>
+<body>
+<h1>{{title}}</h1>
+{{agenda}}
+<footer>Generated on {{today}}</footer>
+</body>
+</html>
+"#;
+
+const STYLE: &str =
+    "body{font-family:sans-serif;max-width:40em;margin:auto;padding:1em;color:#222}\
+h2{border-bottom:1px solid #ccc;margin-top:1.5em}\
+ul{list-style:none;padding:0}\
+li{display:flex;gap:1em;padding:.25em .5em}\
+li.today{background:#ffe9a8;font-weight:bold}\
+.date{min-width:5em}\
+.age{margin-left:auto;color:#666}\
+footer{margin-top:2em;font-size:.8em;color:#666}";
+
+/// Renders an agenda of the events in the given number of months, starting
+/// with the month of `today`, grouped by month.
+///
+/// Events on `today` are highlighted.
+pub fn render_agenda(
+    events: &[ExtractedEvent],
+    today: NaiveDate,
+    months: u32,
+    title: &str,
+    template: &str,
+) -> String {
+    let mut agenda = String::new();
+    let first_month = today.with_day(1).unwrap_or(today);
+
+    for offset in 0..months {
+        let start = first_month + Months::new(offset);
+        let end = start + Months::new(1);
+        let month_events: Vec<&ExtractedEvent> = events
+            .iter()
+            .filter(|event| event.date >= start && event.date < end)
+            .collect();
+
+        agenda += &format!("<section>\n<h2>{}</h2>\n", start.format("%B %Y"));
+
+        if month_events.is_empty() {
+            agenda += "<p>No events</p>\n";
+        } else {
+            agenda += "<ul>\n";
+            for event in month_events {
+                agenda += &format!(
+                    "<li{}><span class=\"date\">{}</span><span class=\"name\">{}</span><span class=\"age\">{}</span></li>\n",
+                    if event.date == today { " class=\"today\"" } else { "" },
+                    event.date.format("%a, %d."),
+                    escape_xml(&event.name),
+                    event.age.map(|age| age.to_string()).unwrap_or_default()
+                );
+            }
+            agenda += "</ul>\n";
+        }
+
+        agenda += "</section>\n";
+    }
+
+    template
+        .replace("{{title}}", &escape_xml(title))
+        .replace("{{style}}", STYLE)
+        .replace("{{today}}", &today.format("%Y-%m-%d").to_string())
+        .replace("{{agenda}}", &agenda)
+}
@@ -0,0 +1,41 @@
+use super::*;
+use crate::testing::event;
+
+#[test]
+fn render_agenda_1() {
+    let today = NaiveDate::from_ymd_opt(2023, 12, 20).unwrap();
+    let events = [
+        event("Past", NaiveDate::from_ymd_opt(2023, 11, 30).unwrap(), None),
+        event("Test <Person>", today, Some(30)),
+        event("Other", NaiveDate::from_ymd_opt(2024, 2, 4).unwrap(), None),
+    ];
+
+    assert_eq!(
+        render_agenda(
+            &events,
+            today,
+            3,
+            "Team & Friends",
+            "<h1>{{title}}</h1>\n{{agenda}}<p>{{today}}</p>"
+        ),
+        "<h1>Team &amp; Friends</h1>\n\
+         <section>\n<h2>December 2023</h2>\n<ul>\n\
+         <li class=\"today\"><span class=\"date\">Wed, 20.</span><span class=\"name\">Test &lt;Person&gt;</span><span class=\"age\">30</span></li>\n\
+         </ul>\n</section>\n\
+         <section>\n<h2>January 2024</h2>\n<p>No events</p>\n</section>\n\
+         <section>\n<h2>February 2024</h2>\n<ul>\n\
+         <li><span class=\"date\">Sun, 04.</span><span class=\"name\">Other</span><span class=\"age\"></span></li>\n\
+         </ul>\n</section>\n\
+         <p>2023-12-20</p>"
+    )
+}
+
+#[test]
+fn render_agenda_default_template() {
+    let today = NaiveDate::from_ymd_opt(2023, 12, 20).unwrap();
+    let html = render_agenda(&[], today, 1, "Birthdays", DEFAULT_TEMPLATE);
+
+    assert!(html.contains("<title>Birthdays</title>"));
+    assert!(html.contains("li.today{"));
+    assert!(!html.contains("{{"));
+}
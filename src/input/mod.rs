@@ -0,0 +1,260 @@
+#[cfg(test)]
+mod tests;
+
+use std::{
+    fs::File,
+    io::{self, BufRead, BufReader, Cursor},
+    path::{Component, Path, PathBuf},
+};
+
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use ical::parser::vcard::component::VcardContact;
+use walkdir::WalkDir;
+
+use crate::{
+    config::ConfigError, config::Entry, csv_import::parse_csv, decode::transcode_vcard,
+    jcard::parse_jcard, ldif::parse_ldif, report::Diagnostic, xcard::parse_xcard,
+    EventExtractorError,
+};
+
+/// Input files found for a configuration entry.
+#[derive(Debug, Default, PartialEq)]
+pub struct InputFiles {
+    pub files: Vec<PathBuf>,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+/// Format of an input file.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum InputFormat {
+    VCard,
+    JCard,
+    XCard,
+    Csv,
+    Ldif,
+}
+
+/// Input path denoting the standard input.
+pub const STDIN: &str = "-";
+
+/// Decides whether a path is an input file of a configuration entry.
+pub struct InputFilter {
+    input: PathBuf,
+    recursive: bool,
+    extensions: Vec<String>,
+    include: Option<GlobSet>,
+    exclude: GlobSet,
+}
+
+impl InputFilter {
+    pub fn new(config_entry: &Entry) -> Result<Self, ConfigError> {
+        Ok(InputFilter {
+            input: normalize(Path::new(&config_entry.input)),
+            recursive: config_entry.recursive,
+            extensions: config_entry.extensions.clone(),
+            include: match config_entry.include.is_empty() {
+                true => None,
+                false => Some(build_glob_set(&config_entry.include)?),
+            },
+            exclude: build_glob_set(&config_entry.exclude)?,
+        })
+    }
+
+    /// Checks the path of a (possibly no longer existing) file.
+    pub fn matches(&self, path: &Path) -> bool {
+        let path = normalize(path);
+
+        if path == self.input {
+            return true;
+        }
+
+        let relative_path = match path.strip_prefix(&self.input) {
+            Ok(relative_path) => relative_path,
+            Err(_) => return false,
+        };
+
+        (self.recursive || relative_path.components().count() == 1)
+            && has_extension(&path, &self.extensions)
+            && self
+                .include
+                .as_ref()
+                .is_none_or(|include| include.is_match(relative_path))
+            && !self.exclude.is_match(relative_path)
+    }
+}
+
+/// Finds the input files of a configuration entry.
+///
+/// The input can be a directory, a single file or "-" for the standard
+/// input. Directories which cannot be read and symbolic link loops are
+/// reported as diagnostics instead of aborting the search.
+pub fn find_input_files(config_entry: &Entry) -> Result<InputFiles, ConfigError> {
+    let filter = InputFilter::new(config_entry)?;
+    let input = Path::new(&config_entry.input);
+    let mut input_files = InputFiles::default();
+
+    if config_entry.input == STDIN || input.is_file() {
+        input_files.files.push(input.to_path_buf());
+        return Ok(input_files);
+    }
+
+    let walker = WalkDir::new(input)
+        .min_depth(1)
+        .max_depth(if config_entry.recursive {
+            usize::MAX
+        } else {
+            1
+        })
+        .follow_links(true)
+        .sort_by_file_name();
+
+    for entry in walker {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(e) => {
+                input_files.diagnostics.push(Diagnostic::UnreadableInput {
+                    path: e
+                        .path()
+                        .map(Path::to_path_buf)
+                        .unwrap_or_else(|| input.to_path_buf()),
+                    message: e.to_string(),
+                });
+                continue;
+            }
+        };
+        let path = entry.path();
+
+        log::debug!(
+            "found entry \"{}\", is file: {}",
+            path.to_string_lossy(),
+            entry.file_type().is_file()
+        );
+
+        if entry.file_type().is_file() && filter.matches(path) {
+            input_files.files.push(path.to_path_buf());
+        }
+    }
+
+    Ok(input_files)
+}
+
+/// Opens an input file returned by `find_input_files`.
+pub fn open_input(path: &Path) -> io::Result<Box<dyn BufRead>> {
+    if path == Path::new(STDIN) {
+        Ok(Box::new(BufReader::new(io::stdin())))
+    } else {
+        Ok(Box::new(BufReader::new(File::open(path)?)))
+    }
+}
+
+/// Determines the format of an input by its extension or, if the extension
+/// is not conclusive, by the beginning of its content.
+pub fn detect_format(path: &Path, buf: &mut dyn BufRead) -> io::Result<InputFormat> {
+    let extension = path
+        .extension()
+        .map(|extension| extension.to_string_lossy().to_lowercase());
+
+    match extension.as_deref() {
+        Some("vcf") | Some("vcard") => return Ok(InputFormat::VCard),
+        Some("jcard") | Some("json") => return Ok(InputFormat::JCard),
+        Some("xcard") | Some("xml") => return Ok(InputFormat::XCard),
+        Some("csv") => return Ok(InputFormat::Csv),
+        Some("ldif") | Some("ldi") => return Ok(InputFormat::Ldif),
+        _ => {}
+    }
+
+    let content = buf.fill_buf()?;
+    let content = content.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(content);
+    let content = match content.iter().position(|byte| !byte.is_ascii_whitespace()) {
+        Some(start) => &content[start..],
+        None => &[],
+    };
+    let starts_with = |prefix: &[u8]| {
+        content.len() >= prefix.len() && content[..prefix.len()].eq_ignore_ascii_case(prefix)
+    };
+
+    Ok(match content.first() {
+        Some(b'[') => InputFormat::JCard,
+        Some(b'<') => InputFormat::XCard,
+        Some(b'#') => InputFormat::Ldif,
+        _ if starts_with(b"dn:") || starts_with(b"version:") => InputFormat::Ldif,
+        _ => InputFormat::VCard,
+    })
+}
+
+/// Parses the contacts of an input in the given format.
+pub fn parse_contacts(
+    mut buf: Box<dyn BufRead>,
+    format: InputFormat,
+    config_entry: &Entry,
+) -> Box<dyn Iterator<Item = Result<VcardContact, EventExtractorError>>> {
+    match format {
+        // vCard files are converted to UTF-8 at once and parsed lazily, all
+        // other formats are parsed at once
+        InputFormat::VCard => match read_vcard(&mut buf) {
+            Ok(text) => Box::new(
+                ical::VcardParser::new(Cursor::new(text))
+                    .map(|contact| contact.map_err(EventExtractorError::from)),
+            ),
+            Err(e) => Box::new(std::iter::once(Err(e))),
+        },
+        InputFormat::JCard => iterate(read_text(&mut buf).and_then(|text| Ok(parse_jcard(&text)?))),
+        InputFormat::XCard => iterate(read_text(&mut buf).and_then(|text| Ok(parse_xcard(&text)?))),
+        InputFormat::Csv => iterate(parse_csv(buf, &config_entry.csv_columns)),
+        InputFormat::Ldif => iterate(parse_ldif(buf, &config_entry.ldif_attributes)),
+    }
+}
+
+fn read_vcard(buf: &mut dyn BufRead) -> Result<String, EventExtractorError> {
+    let mut bytes = Vec::new();
+
+    buf.read_to_end(&mut bytes)?;
+    Ok(transcode_vcard(&bytes)?)
+}
+
+fn read_text(buf: &mut dyn BufRead) -> Result<String, EventExtractorError> {
+    let mut text = String::new();
+
+    buf.read_to_string(&mut text)?;
+    Ok(text)
+}
+
+fn iterate<E: Into<EventExtractorError>>(
+    contacts: Result<Vec<VcardContact>, E>,
+) -> Box<dyn Iterator<Item = Result<VcardContact, EventExtractorError>>> {
+    match contacts {
+        Ok(contacts) => Box::new(contacts.into_iter().map(Ok)),
+        Err(e) => Box::new(std::iter::once(Err(e.into()))),
+    }
+}
+
+/// Removes "." components, e.g. "./contacts/test.vcf" becomes
+/// "contacts/test.vcf".
+fn normalize(path: &Path) -> PathBuf {
+    path.components()
+        .filter(|component| *component != Component::CurDir)
+        .collect()
+}
+
+fn has_extension(path: &Path, extensions: &[String]) -> bool {
+    match path.extension() {
+        Some(extension) => extensions
+            .iter()
+            .any(|elem| extension.eq_ignore_ascii_case(elem.as_str())),
+        None => false,
+    }
+}
+
+fn build_glob_set(patterns: &[String]) -> Result<GlobSet, ConfigError> {
+    let mut builder = GlobSetBuilder::new();
+
+    for pattern in patterns {
+        builder.add(Glob::new(pattern).map_err(|e| {
+            ConfigError::ConfigError(format!("invalid pattern \"{}\": {}", pattern, e))
+        })?);
+    }
+
+    builder
+        .build()
+        .map_err(|e| ConfigError::ConfigError(e.to_string()))
+}
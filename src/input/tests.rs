@@ -0,0 +1,151 @@
+use std::fs::{create_dir_all, File};
+
+use super::*;
+
+fn entry(input: &Path) -> Entry {
+    Entry {
+        input: input.to_string_lossy().into(),
+        output: "/path/output".into(),
+        ..Default::default()
+    }
+}
+
+fn create_files(root: &Path, files: &[&str]) {
+    for file in files {
+        let path = root.join(file);
+
+        create_dir_all(path.parent().unwrap()).unwrap();
+        File::create(path).unwrap();
+    }
+}
+
+#[test]
+fn find_input_files_1() {
+    let dir = tempfile::tempdir().unwrap();
+    create_files(dir.path(), &["a.vcf", "b.txt", "book/c.vcf"]);
+
+    assert_eq!(
+        find_input_files(&entry(dir.path())).unwrap(),
+        InputFiles {
+            files: vec![dir.path().join("a.vcf")],
+            diagnostics: Vec::new()
+        }
+    )
+}
+
+#[test]
+fn find_input_files_2() {
+    let dir = tempfile::tempdir().unwrap();
+    create_files(
+        dir.path(),
+        &[
+            "a.vcf",
+            "book/b.VCARD",
+            "book/c.vcf",
+            "book/archive/d.vcf",
+            "other/e.vcf",
+        ],
+    );
+
+    assert_eq!(
+        find_input_files(&Entry {
+            recursive: true,
+            extensions: vec!["vcf".into(), "vcard".into()],
+            include: vec!["book/**".into()],
+            exclude: vec!["**/archive/**".into()],
+            ..entry(dir.path())
+        })
+        .unwrap(),
+        InputFiles {
+            files: vec![
+                dir.path().join("book/b.VCARD"),
+                dir.path().join("book/c.vcf")
+            ],
+            diagnostics: Vec::new()
+        }
+    )
+}
+
+#[test]
+fn find_input_files_file() {
+    let dir = tempfile::tempdir().unwrap();
+    create_files(dir.path(), &["contacts.txt"]);
+
+    assert_eq!(
+        find_input_files(&entry(&dir.path().join("contacts.txt"))).unwrap(),
+        InputFiles {
+            files: vec![dir.path().join("contacts.txt")],
+            diagnostics: Vec::new()
+        }
+    )
+}
+
+#[test]
+fn find_input_files_stdin() {
+    assert_eq!(
+        find_input_files(&entry(Path::new(STDIN))).unwrap(),
+        InputFiles {
+            files: vec![PathBuf::from(STDIN)],
+            diagnostics: Vec::new()
+        }
+    )
+}
+
+#[cfg(unix)]
+#[test]
+fn find_input_files_symlink_loop() {
+    let dir = tempfile::tempdir().unwrap();
+    create_files(dir.path(), &["book/a.vcf"]);
+    std::os::unix::fs::symlink(dir.path(), dir.path().join("book/loop")).unwrap();
+
+    let input_files = find_input_files(&Entry {
+        recursive: true,
+        ..entry(dir.path())
+    })
+    .unwrap();
+
+    assert_eq!(input_files.files, vec![dir.path().join("book/a.vcf")]);
+    assert!(matches!(
+        input_files.diagnostics.as_slice(),
+        [Diagnostic::UnreadableInput { .. }]
+    ))
+}
+
+#[test]
+fn find_input_files_invalid_pattern() {
+    let dir = tempfile::tempdir().unwrap();
+
+    assert!(find_input_files(&Entry {
+        include: vec!["[".into()],
+        ..entry(dir.path())
+    })
+    .is_err())
+}
+
+#[test]
+fn detect_format_1() {
+    assert_eq!(
+        detect_format(Path::new("contacts.json"), &mut "BEGIN:VCARD".as_bytes()).unwrap(),
+        InputFormat::JCard
+    );
+    assert_eq!(
+        detect_format(Path::new(STDIN), &mut " \n[\"vcard\", []]".as_bytes()).unwrap(),
+        InputFormat::JCard
+    );
+    assert_eq!(
+        detect_format(
+            Path::new("contacts"),
+            &mut "<?xml version=\"1.0\"?>".as_bytes()
+        )
+        .unwrap(),
+        InputFormat::XCard
+    );
+    assert_eq!(
+        detect_format(Path::new(STDIN), &mut "dn: cn=test".as_bytes()).unwrap(),
+        InputFormat::Ldif
+    );
+    assert_eq!(
+        detect_format(Path::new(STDIN), &mut "BEGIN:VCARD".as_bytes()).unwrap(),
+        InputFormat::VCard
+    );
+}
@@ -0,0 +1,167 @@
+#[cfg(test)]
+mod tests;
+
+use ical::{parser::vcard::component::VcardContact, property::Property};
+use serde_json::Value;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum JCardError {
+    #[error("invalid jCard: {}", .0)]
+    InvalidJCard(String),
+    #[error("serde_json error")]
+    SerdeJsonError(#[from] serde_json::Error),
+}
+
+/// Parses a jCard (RFC 7095) document containing a single contact or an
+/// array of contacts.
+///
+/// Properties are converted into their vCard representation, i.e. text
+/// values are escaped and dates use the basic format.
+pub fn parse_jcard(text: &str) -> Result<Vec<VcardContact>, JCardError> {
+    let document: Value = serde_json::from_str(text)?;
+    let array = as_array(&document, "document")?;
+
+    match array.first() {
+        Some(Value::String(name)) if name == "vcard" => Ok(vec![parse_contact(&document)?]),
+        _ => array.iter().map(parse_contact).collect(),
+    }
+}
+
+fn parse_contact(contact: &Value) -> Result<VcardContact, JCardError> {
+    match as_array(contact, "contact")?.as_slice() {
+        [Value::String(name), Value::Array(properties)] if name == "vcard" => Ok(VcardContact {
+            properties: properties
+                .iter()
+                .map(parse_property)
+                .collect::<Result<Vec<Property>, JCardError>>()?,
+        }),
+        _ => Err(JCardError::InvalidJCard(
+            "expected [\"vcard\", [...]]".into(),
+        )),
+    }
+}
+
+fn parse_property(property: &Value) -> Result<Property, JCardError> {
+    match as_array(property, "property")?.as_slice() {
+        [Value::String(name), Value::Object(params), Value::String(value_type), values @ ..]
+            if !values.is_empty() =>
+        {
+            let mut name = name.to_uppercase();
+            let mut param_list = Vec::new();
+
+            for (key, value) in params {
+                match (key.as_str(), value) {
+                    ("group", Value::String(group)) => name = format!("{}.{}", group, name),
+                    (_, Value::Array(values)) => param_list.push((
+                        key.to_uppercase(),
+                        values.iter().map(value_to_string).collect(),
+                    )),
+                    (_, value) => {
+                        param_list.push((key.to_uppercase(), vec![value_to_string(value)]))
+                    }
+                }
+            }
+
+            if value_type != "text" && value_type != "unknown" {
+                let strings: Vec<&str> = values.iter().filter_map(Value::as_str).collect();
+
+                param_list.push(("VALUE".into(), vec![value_type_param(value_type, &strings)]));
+            }
+
+            Ok(Property {
+                name,
+                params: (!param_list.is_empty()).then_some(param_list),
+                value: Some(
+                    values
+                        .iter()
+                        .map(|value| format_value(value, value_type))
+                        .collect::<Vec<String>>()
+                        .join(","),
+                ),
+            })
+        }
+        _ => Err(JCardError::InvalidJCard(format!(
+            "expected [name, {{...}}, type, value] found {}",
+            property
+        ))),
+    }
+}
+
+fn format_value(value: &Value, value_type: &str) -> String {
+    match value {
+        // structured values (e.g. "N" or "ADR")
+        Value::Array(components) => components
+            .iter()
+            .map(|component| match component {
+                Value::Array(items) => items
+                    .iter()
+                    .map(|item| format_value(item, value_type))
+                    .collect::<Vec<String>>()
+                    .join(","),
+                component => format_value(component, value_type),
+            })
+            .collect::<Vec<String>>()
+            .join(";"),
+        Value::String(value) => match value_type {
+            "text" => escape_text(value),
+            "date" | "time" | "date-time" | "date-and-or-time" | "timestamp" => {
+                to_basic_format(value)
+            }
+            _ => value.clone(),
+        },
+        value => value_to_string(value),
+    }
+}
+
+fn value_to_string(value: &Value) -> String {
+    match value {
+        Value::String(value) => value.clone(),
+        value => value.to_string(),
+    }
+}
+
+/// Value of the "VALUE" parameter of a property with the given value type.
+///
+/// Values of the type "date-and-or-time", the default type of "BDAY", are
+/// dates unless they contain a time.
+pub(crate) fn value_type_param(value_type: &str, values: &[&str]) -> String {
+    match value_type {
+        "date-and-or-time" if values.iter().all(|value| !value.contains(['T', ':'])) => {
+            "DATE".into()
+        }
+        value_type => value_type.to_uppercase(),
+    }
+}
+
+/// Escapes a text value as it would appear in a vCard.
+pub(crate) fn escape_text(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace(',', "\\,")
+        .replace(';', "\\;")
+        .replace('\n', "\\n")
+}
+
+/// Converts a date or time in the extended format (e.g. "1985-04-12" or
+/// "--04-12") into the basic format used by vCard.
+fn to_basic_format(value: &str) -> String {
+    let (prefix, rest) = match value.strip_prefix("--") {
+        Some(rest) => ("--", rest),
+        None => ("", value),
+    };
+    let (date, time) = match rest.split_once('T') {
+        Some((date, time)) => (date.replace('-', ""), format!("T{}", time.replace(':', ""))),
+        None if rest.contains(':') => (String::new(), rest.replace(':', "")),
+        None => (rest.replace('-', ""), String::new()),
+    };
+
+    format!("{}{}{}", prefix, date, time)
+}
+
+fn as_array<'a>(value: &'a Value, context: &str) -> Result<&'a Vec<Value>, JCardError> {
+    value.as_array().ok_or(JCardError::InvalidJCard(format!(
+        "{} is not an array",
+        context
+    )))
+}
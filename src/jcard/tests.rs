@@ -0,0 +1,125 @@
+use super::*;
+use crate::ExtractedDate;
+
+#[test]
+fn parse_jcard_1() {
+    let text = r#"
+        ["vcard",
+          [
+            ["version", {}, "text", "4.0"],
+            ["fn", {"language": "de", "pref": "1"}, "text", "Doe, J."],
+            ["n", {}, "text", ["Doe", "J.", "", "", ["ing. jr", "M.Sc."]]],
+            ["bday", {}, "date", "1990-10-05"],
+            ["anniversary", {}, "date", "--10-05"],
+            ["tel", {"group": "item1", "type": ["work", "voice"]}, "uri", "tel:+1-555-555-5555"],
+            ["uid", {}, "text", "test_uid"]
+          ]
+        ]
+    "#;
+
+    assert_eq!(
+        format!("{:?}", parse_jcard(text).unwrap()),
+        format!(
+            "{:?}",
+            vec![VcardContact {
+                properties: vec![
+                    Property {
+                        name: "VERSION".into(),
+                        params: None,
+                        value: Some("4.0".into())
+                    },
+                    Property {
+                        name: "FN".into(),
+                        params: Some(vec![
+                            ("LANGUAGE".into(), vec!["de".into()]),
+                            ("PREF".into(), vec!["1".into()])
+                        ]),
+                        value: Some("Doe\\, J.".into())
+                    },
+                    Property {
+                        name: "N".into(),
+                        params: None,
+                        value: Some("Doe;J.;;;ing. jr,M.Sc.".into())
+                    },
+                    Property {
+                        name: "BDAY".into(),
+                        params: Some(vec![("VALUE".into(), vec!["DATE".into()])]),
+                        value: Some("19901005".into())
+                    },
+                    Property {
+                        name: "ANNIVERSARY".into(),
+                        params: Some(vec![("VALUE".into(), vec!["DATE".into()])]),
+                        value: Some("--1005".into())
+                    },
+                    Property {
+                        name: "item1.TEL".into(),
+                        params: Some(vec![
+                            ("TYPE".into(), vec!["work".into(), "voice".into()]),
+                            ("VALUE".into(), vec!["URI".into()])
+                        ]),
+                        value: Some("tel:+1-555-555-5555".into())
+                    },
+                    Property {
+                        name: "UID".into(),
+                        params: None,
+                        value: Some("test_uid".into())
+                    },
+                ]
+            }]
+        )
+    )
+}
+
+#[test]
+fn parse_jcard_2() {
+    let text = r#"
+        [
+          ["vcard", [["fn", {}, "text", "A"]]],
+          ["vcard", [["fn", {}, "text", "B"]]]
+        ]
+    "#;
+
+    assert_eq!(parse_jcard(text).unwrap().len(), 2)
+}
+
+#[test]
+fn parse_jcard_invalid() {
+    assert!(parse_jcard(r#"["vcard", [["fn", {}]]]"#).is_err())
+}
+
+#[test]
+fn to_basic_format_1() {
+    assert_eq!(to_basic_format("1985-04-12"), "19850412");
+    assert_eq!(to_basic_format("--04-12"), "--0412");
+    assert_eq!(to_basic_format("1985-04-12T23:20:50Z"), "19850412T232050Z");
+    assert_eq!(to_basic_format("23:20:50"), "232050");
+}
+
+#[test]
+fn parse_jcard_3() {
+    let text = r#"
+        ["vcard", [
+          ["fn", {}, "text", "Test Person"],
+          ["bday", {}, "date-and-or-time", "--10-05"],
+          ["anniversary", {}, "date-and-or-time", "2009-08-08T14:30:00-05:00"]
+        ]]
+    "#;
+    let contact = &parse_jcard(text).unwrap()[0];
+
+    assert_eq!(
+        contact.properties[1].params,
+        Some(vec![("VALUE".into(), vec!["DATE".into()])])
+    );
+    assert_eq!(
+        contact.properties[2].params,
+        Some(vec![("VALUE".into(), vec!["DATE-AND-OR-TIME".into()])])
+    );
+    assert_eq!(
+        ExtractedDate::try_from(&contact.properties[1]).unwrap(),
+        ExtractedDate {
+            year: None,
+            month: 10,
+            day: 5
+        }
+    );
+}
@@ -0,0 +1,188 @@
+#[cfg(test)]
+mod tests;
+
+use std::io::BufRead;
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use ical::{parser::vcard::component::VcardContact, property::Property};
+use thiserror::Error;
+
+use crate::{config::LdifAttributes, csv_import::parse_date, jcard::escape_text};
+
+#[derive(Error, Debug)]
+pub enum LdifError {
+    #[error("invalid LDIF in line {}: {}", .0, .1)]
+    InvalidLdif(usize, String),
+    #[error("std::io error")]
+    StdIoError(#[from] std::io::Error),
+}
+
+/// Attribute of an LDIF entry.
+#[derive(Debug, PartialEq)]
+struct Attribute {
+    name: String,
+    options: Vec<String>,
+    value: String,
+}
+
+/// Parses an LDIF (RFC 2849) file into contacts, using the given mapping of
+/// attributes to properties.
+///
+/// Entries without a name are skipped. If an entry has no identifier
+/// attribute, its distinguished name is used instead. Language options (e.g.
+/// "cn;lang-de") are converted into "LANGUAGE" parameters.
+pub fn parse_ldif(
+    reader: impl BufRead,
+    attributes: &LdifAttributes,
+) -> Result<Vec<VcardContact>, LdifError> {
+    let mut contacts = Vec::new();
+
+    for entry in read_entries(reader)? {
+        let dn = match entry
+            .iter()
+            .find(|attribute| attribute.name.eq_ignore_ascii_case("dn"))
+        {
+            Some(dn) => dn.value.clone(),
+            // e.g. the "version" line
+            None => continue,
+        };
+        let mut properties = Vec::new();
+        let mut uid = None;
+
+        for attribute in &entry {
+            if attribute.name.eq_ignore_ascii_case(&attributes.name) {
+                let languages: Vec<String> = attribute
+                    .options
+                    .iter()
+                    .filter_map(|option| option.strip_prefix("lang-"))
+                    .map(|language| language.to_string())
+                    .collect();
+
+                properties.push(Property {
+                    name: "FN".into(),
+                    params: languages
+                        .first()
+                        .map(|language| vec![("LANGUAGE".into(), vec![language.clone()])]),
+                    value: Some(escape_text(&attribute.value)),
+                });
+            } else if attribute.name.eq_ignore_ascii_case(&attributes.uid) {
+                uid = Some(attribute.value.clone());
+            } else if attribute.name.eq_ignore_ascii_case(&attributes.birthday) {
+                match parse_generalized_time(&attribute.value) {
+                    Some(date) => properties.push(Property {
+                        name: "BDAY".into(),
+                        params: Some(vec![("VALUE".into(), vec!["DATE".into()])]),
+                        value: Some(date),
+                    }),
+                    None => log::warn!(
+                        "ignoring unexpected date \"{}\" of \"{}\"",
+                        attribute.value,
+                        dn
+                    ),
+                }
+            }
+        }
+
+        if !properties.iter().any(|property| property.name == "FN") {
+            log::warn!("skipping entry \"{}\" without a name", dn);
+            continue;
+        }
+
+        properties.push(Property {
+            name: "UID".into(),
+            params: None,
+            value: Some(uid.unwrap_or(dn)),
+        });
+        contacts.push(VcardContact { properties });
+    }
+
+    Ok(contacts)
+}
+
+/// Converts a date given as LDAP generalized time (e.g. "19901005000000Z")
+/// or in one of the formats accepted for CSV files.
+fn parse_generalized_time(value: &str) -> Option<String> {
+    match value.get(..8) {
+        Some(date)
+            if value.len() >= 10 && value.bytes().take(10).all(|byte| byte.is_ascii_digit()) =>
+        {
+            parse_date(date)
+        }
+        _ => parse_date(value),
+    }
+}
+
+/// Reads the entries of an LDIF file, unfolding continuation lines and
+/// decoding base64 encoded values.
+fn read_entries(reader: impl BufRead) -> Result<Vec<Vec<Attribute>>, LdifError> {
+    let mut entries = Vec::new();
+    let mut entry = Vec::new();
+    let mut lines: Vec<(usize, String)> = Vec::new();
+
+    for (index, line) in reader.lines().enumerate() {
+        let line = line?;
+
+        if let Some(continuation) = line.strip_prefix(' ') {
+            match lines.last_mut() {
+                Some((_, last)) => last.push_str(continuation),
+                None => {
+                    return Err(LdifError::InvalidLdif(
+                        index + 1,
+                        "continuation without a preceding line".into(),
+                    ))
+                }
+            }
+        } else {
+            lines.push((index + 1, line));
+        }
+    }
+
+    for (number, line) in lines {
+        if line.trim().is_empty() {
+            if !entry.is_empty() {
+                entries.push(entry);
+                entry = Vec::new();
+            }
+        } else if !line.starts_with('#') {
+            entry.push(parse_attribute(number, &line)?);
+        }
+    }
+
+    if !entry.is_empty() {
+        entries.push(entry);
+    }
+
+    Ok(entries)
+}
+
+fn parse_attribute(number: usize, line: &str) -> Result<Attribute, LdifError> {
+    let (description, value) = line
+        .split_once(':')
+        .ok_or(LdifError::InvalidLdif(number, "missing \":\"".into()))?;
+    let mut description = description.split(';');
+    let name = description.next().unwrap_or_default().to_string();
+    let options = description.map(|option| option.to_lowercase()).collect();
+
+    let value = if let Some(encoded) = value.strip_prefix(':') {
+        let bytes = STANDARD
+            .decode(encoded.trim())
+            .map_err(|e| LdifError::InvalidLdif(number, e.to_string()))?;
+
+        String::from_utf8(bytes).map_err(|e| LdifError::InvalidLdif(number, e.to_string()))?
+    } else if value.starts_with('<') {
+        log::warn!(
+            "ignoring URL value of attribute \"{}\" in line {}",
+            name,
+            number
+        );
+        String::new()
+    } else {
+        value.trim_start().to_string()
+    };
+
+    Ok(Attribute {
+        name,
+        options,
+        value,
+    })
+}
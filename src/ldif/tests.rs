@@ -0,0 +1,112 @@
+use super::*;
+
+#[test]
+fn parse_ldif_1() {
+    let text = "version: 1\n\
+                \n\
+                # test person\n\
+                dn: uid=tperson,ou=people,dc=example,dc=com\n\
+                objectClass: inetOrgPerson\n\
+                cn: Test Person\n\
+                cn;lang-de:: VMOkc3RwZXJzb24=\n\
+                entryUUID: 597ae2f6-16a6-1027-98f4-\n \
+                abcdef012345\n\
+                birthDate: 1990-10-05\n\
+                \n\
+                dn: uid=other,ou=people,dc=example,dc=com\n\
+                cn: Other, Person\n\
+                birthDate: 19801231000000Z\n\
+                \n\
+                dn: ou=people,dc=example,dc=com\n\
+                objectClass: organizationalUnit\n";
+
+    assert_eq!(
+        format!(
+            "{:?}",
+            parse_ldif(text.as_bytes(), &LdifAttributes::default()).unwrap()
+        ),
+        format!(
+            "{:?}",
+            vec![
+                VcardContact {
+                    properties: vec![
+                        Property {
+                            name: "FN".into(),
+                            params: None,
+                            value: Some("Test Person".into())
+                        },
+                        Property {
+                            name: "FN".into(),
+                            params: Some(vec![("LANGUAGE".into(), vec!["de".into()])]),
+                            value: Some("Tästperson".into())
+                        },
+                        Property {
+                            name: "BDAY".into(),
+                            params: Some(vec![("VALUE".into(), vec!["DATE".into()])]),
+                            value: Some("19901005".into())
+                        },
+                        Property {
+                            name: "UID".into(),
+                            params: None,
+                            value: Some("597ae2f6-16a6-1027-98f4-abcdef012345".into())
+                        },
+                    ]
+                },
+                VcardContact {
+                    properties: vec![
+                        Property {
+                            name: "FN".into(),
+                            params: None,
+                            value: Some("Other\\, Person".into())
+                        },
+                        Property {
+                            name: "BDAY".into(),
+                            params: Some(vec![("VALUE".into(), vec!["DATE".into()])]),
+                            value: Some("19801231".into())
+                        },
+                        Property {
+                            name: "UID".into(),
+                            params: None,
+                            value: Some("uid=other,ou=people,dc=example,dc=com".into())
+                        },
+                    ]
+                }
+            ]
+        )
+    )
+}
+
+#[test]
+fn parse_ldif_2() {
+    let text = "dn: uid=tperson,ou=people,dc=example,dc=com\n\
+                displayName: Test Person\n\
+                employeeNumber: 42\n\
+                x-birthday: --10-05\n";
+    let contacts = parse_ldif(
+        text.as_bytes(),
+        &LdifAttributes {
+            name: "displayName".into(),
+            uid: "employeeNumber".into(),
+            birthday: "x-birthday".into(),
+        },
+    )
+    .unwrap();
+
+    assert_eq!(
+        contacts[0]
+            .properties
+            .iter()
+            .map(|property| property.value.clone().unwrap())
+            .collect::<Vec<String>>(),
+        vec![
+            "Test Person".to_string(),
+            "--1005".to_string(),
+            "42".to_string()
+        ]
+    )
+}
+
+#[test]
+fn parse_ldif_invalid() {
+    assert!(parse_ldif(" continuation\n".as_bytes(), &LdifAttributes::default()).is_err())
+}
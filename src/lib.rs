@@ -1,13 +1,40 @@
+pub mod cache;
+pub mod caldav;
+pub mod carddav;
 pub mod config;
+pub mod csv_import;
+pub mod daemon;
+pub mod dav;
+pub mod decode;
+pub mod dedupe;
+pub mod export;
+pub mod feed;
+pub mod filter;
+pub mod groups;
+pub mod html;
+pub mod input;
+pub mod jcard;
+pub mod ldif;
+pub mod lock;
+pub mod parallel;
+pub mod params;
+pub mod report;
+pub mod select;
 pub mod ser;
+pub mod serve;
+#[cfg(test)]
+mod testing;
+pub mod upcoming;
+pub mod watch;
+pub mod xcard;
 use std::{
-    fs::{read_dir, remove_file, File},
-    io::{BufReader, BufWriter, Write},
-    path::PathBuf,
+    fs::{read_dir, read_to_string, remove_file, File},
+    io::{self, BufWriter, Cursor, Write},
+    path::{Path, PathBuf},
 };
 
-use chrono::{DateTime, Datelike, Duration, TimeZone, Utc};
-use config::Entry;
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, TimeZone, Utc};
+use config::{Entry, OutputFormat};
 use ical::{
     parser::{
         ical::component::{IcalCalendar, IcalEvent},
@@ -15,9 +42,52 @@ use ical::{
     },
     property::Property,
 };
+use serde::Serialize;
 use thiserror::Error;
 
-use crate::ser::calendar_to_string;
+use crate::{
+    cache::extract_cached,
+    caldav::upload,
+    carddav::fetch_address_book,
+    dav::{is_collection_url, DavClient},
+    decode::decode_property,
+    dedupe::deduplicate_entry,
+    export::{write_csv, write_json_lines},
+    feed::render_feed,
+    filter::{ContactFilter, Group},
+    groups::write_group_outputs,
+    html::{render_agenda, DEFAULT_TEMPLATE},
+    input::{detect_format, find_input_files, open_input, parse_contacts, InputFormat, STDIN},
+    lock::lock_output,
+    parallel::map_parallel,
+    params::map_text_params,
+    report::{Diagnostic, Report},
+    select::{select_date, select_property},
+    ser::{calendar_to_string, serialize_calendar, unescape_text},
+};
+
+/// Output path denoting the standard output.
+pub const STDOUT: &str = "-";
+
+/// Event extracted from a contact.
+#[derive(Debug, Clone)]
+pub struct ExtractedEvent {
+    pub uid: String,
+    pub contact_uid: String,
+    pub name: String,
+    pub kind: EventKind,
+    pub date: NaiveDate,
+    /// Age (e.g. of the person having their birthday), if the year is known.
+    pub age: Option<i32>,
+    pub source: PathBuf,
+    pub event: IcalEvent,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EventKind {
+    Birthday,
+}
 
 #[derive(Debug, PartialEq)]
 struct ExtractedDate {
@@ -42,35 +112,78 @@ pub enum EventExtractorError {
     SerializationError(#[from] ser::SerializationError),
     #[error("configuration error")]
     ConfigError(#[from] config::ConfigError),
+    #[error("decoding error")]
+    DecodeError(#[from] decode::DecodeError),
     #[error("std::io error")]
     StdIoError(#[from] std::io::Error),
     #[error("ical::parser parse error")]
     IcalParseError(#[from] ical::parser::ParserError),
+    #[error("export error")]
+    ExportError(#[from] export::ExportError),
+    #[error("CSV import error")]
+    CsvImportError(#[from] csv_import::CsvImportError),
+    #[error("jCard parse error")]
+    JCardError(#[from] jcard::JCardError),
+    #[error("LDIF parse error")]
+    LdifError(#[from] ldif::LdifError),
+    #[error("xCard parse error")]
+    XCardError(#[from] xcard::XCardError),
+    #[error("server error")]
+    ServeError(#[from] serve::ServeError),
+    #[error("WebDAV error: {}", .0)]
+    DavError(#[from] dav::DavError),
+    #[error("CardDAV error: {}", .0)]
+    CarddavError(#[from] carddav::CarddavError),
+    #[error("watch error")]
+    WatchError(#[from] watch::WatchError),
+    #[error("cache error")]
+    CacheError(#[from] cache::CacheError),
+    #[error("lock error")]
+    LockError(#[from] lock::LockError),
+    #[error("filter error")]
+    FilterError(#[from] filter::FilterError),
 }
 
-pub fn process_entry(config_entry: &Entry) -> Result<(), EventExtractorError> {
-    if config_entry.remove_files {
-        // remove existing files
-        log::info!("removing exiting files");
-        for entry in read_dir(&config_entry.output)? {
-            let path = entry?.path();
-
-            if path.is_file()
-                && match path.extension() {
-                    Some(extension) => extension == "ics",
-                    None => false,
-                }
-            {
-                remove_file(path)?
-            }
+pub fn process_entry(config_entry: &Entry) -> Result<Report, EventExtractorError> {
+    process_merged(&[config_entry])
+}
+
+/// Groups configuration entries for processing: deduplicated entries with
+/// the same output are processed together, all other entries on their own.
+pub fn merge_entries(entries: &[Entry]) -> Vec<Vec<&Entry>> {
+    let mut merged: Vec<Vec<&Entry>> = Vec::new();
+
+    for entry in entries {
+        match merged.iter_mut().find(|group| {
+            entry.deduplicate && group[0].deduplicate && group[0].output == entry.output
+        }) {
+            Some(group) => group.push(entry),
+            None => merged.push(vec![entry]),
         }
     }
 
-    let current_year = Utc::now().year();
-    let years: Vec<i32> = vec![-1, 0, 1, 2]
-        .iter()
-        .map(|offset| current_year + offset)
-        .collect();
+    merged
+}
+
+/// Extracts the events of configuration entries and writes them into the
+/// output of the first entry, using its settings.
+///
+/// If the first entry is deduplicated, the events of duplicate contacts are
+/// removed.
+pub fn process_merged(entries: &[&Entry]) -> Result<Report, EventExtractorError> {
+    let config_entry = entries[0];
+    let Some(_lock) = lock_output(config_entry)? else {
+        let diagnostic = Diagnostic::OutputLocked {
+            path: PathBuf::from(&config_entry.output),
+        };
+
+        log::warn!("{}", diagnostic);
+        return Ok(Report {
+            diagnostics: vec![diagnostic],
+            ..Default::default()
+        });
+    };
+    let years = event_years();
     log::info!(
         "generating entries for years: {}",
         years
@@ -80,93 +193,412 @@ pub fn process_entry(config_entry: &Entry) -> Result<(), EventExtractorError> {
             .join(", ")
     );
 
-    // create new files
-    for entry in read_dir(&config_entry.input)? {
-        let path = entry?.path();
-
-        log::debug!(
-            "found entry \"{}\", is file: {}, ends with .vcf: {}",
-            path.to_string_lossy(),
-            path.is_file(),
-            match path.extension() {
-                Some(extension) => extension == "vcf",
-                None => false,
+    let (events, mut report) = extract_merged_with(entries, |entry| match &entry.cache {
+        Some(cache_path) if entry.input != STDIN && !is_collection_url(&entry.input) => {
+            extract_cached(entry, cache_path, &years)
+        }
+        _ => extract_entry(entry, &years),
+    })?;
+
+    let groups = report.groups.clone();
+
+    write_events(config_entry, &events, &mut report)?;
+    write_group_outputs(config_entry, &events, &groups, &mut report)?;
+
+    Ok(report)
+}
+
+/// Extracts the events of configuration entries processed together, see
+/// `merge_entries`.
+///
+/// If the first entry is deduplicated, the events of duplicate contacts are
+/// removed. The events are sorted by date and identifier.
+pub fn extract_merged(
+    entries: &[&Entry],
+    years: &[i32],
+) -> Result<(Vec<ExtractedEvent>, Report), EventExtractorError> {
+    extract_merged_with(entries, |entry| extract_entry(entry, years))
+}
+
+/// Extracts the events of configuration entries processed together like
+/// `extract_merged`, using the given function for the events of an entry.
+fn extract_merged_with(
+    entries: &[&Entry],
+    mut extract_entry: impl FnMut(&Entry) -> Result<(Vec<ExtractedEvent>, Report), EventExtractorError>,
+) -> Result<(Vec<ExtractedEvent>, Report), EventExtractorError> {
+    let mut events = Vec::new();
+    let mut report = Report::default();
+
+    for entry in entries {
+        let (mut entry_events, entry_report) = extract_entry(entry)?;
+
+        events.append(&mut entry_events);
+        report.merge(entry_report);
+    }
+
+    let mut events = deduplicate_entry(entries[0], events, &mut report);
+
+    events.sort_by(|a, b| (a.date, &a.uid).cmp(&(b.date, &b.uid)));
+    report.events = events.len();
+
+    Ok((events, report))
+}
+
+/// Writes the events of a configuration entry in its output format.
+pub fn write_events(
+    config_entry: &Entry,
+    events: &[ExtractedEvent],
+    report: &mut Report,
+) -> Result<(), EventExtractorError> {
+    match config_entry.output_format {
+        OutputFormat::Csv => write_output(&config_entry.output, |writer| {
+            Ok(write_csv(events, writer)?)
+        })?,
+        OutputFormat::JsonLines => write_output(&config_entry.output, |writer| {
+            Ok(write_json_lines(events, writer)?)
+        })?,
+        OutputFormat::Html => {
+            let template = match &config_entry.html.template {
+                Some(template) => read_to_string(template)?,
+                None => DEFAULT_TEMPLATE.to_string(),
+            };
+            let html = render_agenda(
+                events,
+                Local::now().date_naive(),
+                config_entry.html.months,
+                &config_entry.html.title,
+                &template,
+            );
+
+            write_output(&config_entry.output, |writer| {
+                Ok(writer.write_all(html.as_bytes())?)
+            })?
+        }
+        OutputFormat::Atom => {
+            let feed = render_feed(
+                events,
+                Local::now().date_naive(),
+                config_entry.feed.days,
+                &config_entry.feed.title,
+            );
+
+            write_output(&config_entry.output, |writer| {
+                Ok(writer.write_all(feed.as_bytes())?)
+            })?
+        }
+        OutputFormat::ICalendar if is_collection_url(&config_entry.output) => {
+            let client = DavClient::new(
+                &config_entry.output,
+                config_entry.output_credentials.as_ref(),
+            )?;
+            let resources = events
+                .iter()
+                .map(|event| {
+                    Ok((
+                        event_file_name(&event.uid, OutputFormat::ICalendar),
+                        calendar_to_string(&wrap_event(event.event.clone()))?,
+                    ))
+                })
+                .collect::<Result<Vec<_>, EventExtractorError>>()?;
+            let upload = upload(&client, &resources, config_entry.remove_files)?;
+
+            log::info!(
+                "uploaded events to \"{}\": {} created, {} updated, {} deleted",
+                config_entry.output,
+                upload.created,
+                upload.updated,
+                upload.deleted
+            );
+            report.diagnostics.extend(upload.diagnostics);
+        }
+        output_format => {
+            if config_entry.remove_files {
+                // remove existing files
+                log::info!("removing exiting files");
+                for entry in read_dir(&config_entry.output)? {
+                    let path = entry?.path();
+
+                    if path.is_file()
+                        && match path.extension() {
+                            Some(extension) => extension == output_format.extension(),
+                            None => false,
+                        }
+                    {
+                        remove_file(path)?
+                    }
+                }
             }
-        );
 
-        if path.is_file()
-            && match path.extension() {
-                Some(extension) => extension == "vcf",
-                None => false,
+            // create new files
+            for event in events {
+                write_event_file(config_entry, event)?;
             }
-        {
-            log::info!("processing file \"{}\"", path.to_string_lossy());
-
-            let buf = BufReader::new(File::open(&path).unwrap());
-            let reader = ical::VcardParser::new(buf);
-
-            for vcard in reader {
-                let contact = vcard?;
-                for event in convert(&contact, &years)? {
-                    let uid = event
-                        .properties
-                        .iter()
-                        .find(|&elem| elem.name == "UID")
-                        .ok_or(EventExtractorError::PropertyNotFound("UID".into()))?
-                        .value
-                        .as_ref()
-                        .ok_or(EventExtractorError::PropertyValueNotFound("UID".into()))?
-                        .clone();
-                    let mut filename = PathBuf::from(&config_entry.output);
-                    filename.push(format!("{}.ics", uid));
+        }
+    }
 
-                    let cal = IcalCalendar {
-                        properties: vec![
-                            Property {
-                                name: "VERSION".into(),
-                                value: Some("2.0".into()),
-                                ..Default::default()
-                            },
-                            Property {
-                                name: "PRODID".into(),
-                                value: Some("event-extractor//hochreiner.net".into()),
-                                ..Default::default()
-                            },
-                        ],
-                        events: vec![event],
-                        ..Default::default()
-                    };
+    Ok(())
+}
 
-                    let mut writer = BufWriter::new(File::create(filename)?);
+/// Checks whether the events of a configuration entry are written into
+/// files of their own, i.e. whether single events can be updated.
+pub fn writes_event_files(config_entry: &Entry) -> bool {
+    matches!(
+        config_entry.output_format,
+        OutputFormat::ICalendar | OutputFormat::JCal | OutputFormat::XCal
+    ) && !is_collection_url(&config_entry.output)
+}
 
-                    writer.write_all(calendar_to_string(&cal)?.as_bytes())?;
-                }
+/// Path of the file of an event in the output directory.
+pub fn event_file_path(config_entry: &Entry, uid: &str) -> PathBuf {
+    Path::new(&config_entry.output).join(event_file_name(uid, config_entry.output_format))
+}
+
+/// Writes an event into a file of its own in the output directory.
+pub fn write_event_file(
+    config_entry: &Entry,
+    event: &ExtractedEvent,
+) -> Result<(), EventExtractorError> {
+    let mut writer = BufWriter::new(File::create(event_file_path(config_entry, &event.uid))?);
+
+    writer.write_all(
+        serialize_calendar(&wrap_event(event.event.clone()), config_entry.output_format)?
+            .as_bytes(),
+    )?;
+    Ok(writer.flush()?)
+}
+
+/// Name of the file (or CalDAV resource) of an event.
+pub fn event_file_name(uid: &str, output_format: OutputFormat) -> String {
+    format!("{}.{}", uid, output_format.extension())
+}
+
+/// Years for which events are generated: the previous, the current and the
+/// next two years.
+pub fn event_years() -> Vec<i32> {
+    let current_year = Local::now().year();
+
+    [-1, 0, 1, 2]
+        .iter()
+        .map(|offset| current_year + offset)
+        .collect()
+}
+
+/// Extracts the events of all contacts of a configuration entry.
+///
+/// The events are sorted by date and identifier.
+pub fn extract_entry(
+    config_entry: &Entry,
+    years: &[i32],
+) -> Result<(Vec<ExtractedEvent>, Report), EventExtractorError> {
+    let filter = ContactFilter::new(config_entry)?;
+
+    extract_entry_with(config_entry, years, &filter, |path| {
+        extract_file(config_entry, path, years, &filter)
+    })
+}
+
+/// Extracts the events of all contacts of a configuration entry, using the
+/// given function for the events of an input file.
+///
+/// The input files are processed in parallel, but the result is the same as
+/// if they were processed one after the other: the error of the first
+/// failing file is returned.
+pub(crate) fn extract_entry_with(
+    config_entry: &Entry,
+    years: &[i32],
+    filter: &ContactFilter,
+    extract_file: impl Fn(&Path) -> Result<(Vec<ExtractedEvent>, Report), EventExtractorError> + Sync,
+) -> Result<(Vec<ExtractedEvent>, Report), EventExtractorError> {
+    let mut report = Report::default();
+    let mut events = Vec::new();
+
+    if is_collection_url(&config_entry.input) {
+        for (href, data) in fetch_address_book(config_entry)? {
+            let contacts = parse_contacts(
+                Box::new(Cursor::new(data.into_bytes())),
+                InputFormat::VCard,
+                config_entry,
+            );
+
+            extract_contacts(
+                contacts,
+                Path::new(&href),
+                config_entry,
+                years,
+                filter,
+                &mut events,
+                &mut report,
+            )?;
+        }
+    } else {
+        let input_files = find_input_files(config_entry)?;
+
+        for diagnostic in input_files.diagnostics {
+            log::warn!("{}", diagnostic);
+            report.diagnostics.push(diagnostic);
+        }
+
+        let results = map_parallel(&input_files.files, config_entry.jobs(), |path| {
+            extract_file(path)
+        });
+
+        for result in results {
+            let (mut file_events, file_report) = result?;
+
+            events.append(&mut file_events);
+            report.merge(file_report);
+        }
+    }
+
+    if report.date_conflicts() > 0 {
+        log::warn!(
+            "found {} contacts with conflicting dates in \"{}\"",
+            report.date_conflicts(),
+            config_entry.input
+        );
+    }
+
+    events = filter.filter_groups(events, &report.groups);
+    events.sort_by(|a, b| (a.date, &a.uid).cmp(&(b.date, &b.uid)));
+    report.events = events.len();
+
+    Ok((events, report))
+}
+
+/// Extracts the events of all contacts of a single input file.
+///
+/// A file which cannot be opened is reported as a diagnostic.
+pub fn extract_file(
+    config_entry: &Entry,
+    path: &Path,
+    years: &[i32],
+    filter: &ContactFilter,
+) -> Result<(Vec<ExtractedEvent>, Report), EventExtractorError> {
+    let mut report = Report::default();
+    let mut events = Vec::new();
+
+    log::info!("processing file \"{}\"", path.to_string_lossy());
+
+    let mut buf = match open_input(path) {
+        Ok(buf) => buf,
+        Err(e) => {
+            let diagnostic = Diagnostic::UnreadableInput {
+                path: path.to_path_buf(),
+                message: e.to_string(),
+            };
+
+            log::warn!("{}", diagnostic);
+            report.diagnostics.push(diagnostic);
+            return Ok((events, report));
+        }
+    };
+    let format = detect_format(path, &mut buf)?;
+    log::debug!("detected format {:?}", format);
+
+    extract_contacts(
+        parse_contacts(buf, format, config_entry),
+        path,
+        config_entry,
+        years,
+        filter,
+        &mut events,
+        &mut report,
+    )?;
+    report.events = events.len();
+
+    Ok((events, report))
+}
+
+pub(crate) fn extract_contacts(
+    contacts: impl Iterator<Item = Result<VcardContact, EventExtractorError>>,
+    path: &Path,
+    config_entry: &Entry,
+    years: &[i32],
+    filter: &ContactFilter,
+    events: &mut Vec<ExtractedEvent>,
+    report: &mut Report,
+) -> Result<(), EventExtractorError> {
+    let language = config_entry.preferred_language.as_deref();
+
+    for contact in contacts {
+        let contact = contact?;
+
+        report.contacts += 1;
+        report.groups.extend(Group::from_contact(&contact));
+
+        if let Some(diagnostic) = find_date_conflict(&contact, language, path)? {
+            if config_entry.report_date_conflicts {
+                log::warn!("{}", diagnostic);
             }
+            report.diagnostics.push(diagnostic);
         }
+
+        events.append(&mut extract_events(
+            &contact, years, language, filter, path,
+        )?);
     }
 
     Ok(())
 }
 
+/// Wraps an event into a calendar of its own.
+pub fn wrap_event(event: IcalEvent) -> IcalCalendar {
+    wrap_events(vec![event])
+}
+
+/// Wraps events into a single calendar.
+pub fn wrap_events(events: Vec<IcalEvent>) -> IcalCalendar {
+    IcalCalendar {
+        properties: vec![
+            Property {
+                name: "VERSION".into(),
+                value: Some("2.0".into()),
+                ..Default::default()
+            },
+            Property {
+                name: "PRODID".into(),
+                value: Some("event-extractor//hochreiner.net".into()),
+                ..Default::default()
+            },
+        ],
+        events,
+        ..Default::default()
+    }
+}
+
+/// Writes to the given file or, for "-", to the standard output.
+fn write_output(
+    output: &str,
+    write: impl FnOnce(&mut dyn Write) -> Result<(), EventExtractorError>,
+) -> Result<(), EventExtractorError> {
+    if output == STDOUT {
+        write(&mut io::stdout().lock())
+    } else {
+        let mut writer = BufWriter::new(File::create(output)?);
+
+        write(&mut writer)?;
+        Ok(writer.flush()?)
+    }
+}
+
+/// Generates the events of a contact, unless it is excluded by the filter.
 pub fn convert(
     contact: &VcardContact,
-    years: &Vec<i32>,
+    years: &[i32],
+    language: Option<&str>,
+    filter: &ContactFilter,
 ) -> Result<Vec<IcalEvent>, EventExtractorError> {
-    let mut fn_prop = None;
-    let mut bday_prop = None;
-    let mut uid_prop = None;
+    if !filter.matches(contact) {
+        return Ok(Vec::new());
+    }
+
+    let props = ContactProperties::try_from(contact)?;
     let timestamp = Utc::now();
 
-    for prop in &contact.properties {
-        match prop.name.as_str() {
-            "FN" => fn_prop = Some(prop),
-            "BDAY" => bday_prop = Some(prop),
-            "UID" => uid_prop = Some(prop),
-            _ => {}
-        }
-    }
+    let fn_prop = select_property(&props.fn_props.iter().collect::<Vec<_>>(), language);
+    let (bday_prop, _) = select_date(&props.bday_props.iter().collect::<Vec<_>>(), language);
 
-    match (fn_prop, uid_prop, bday_prop) {
+    match (fn_prop, &props.uid_prop, bday_prop) {
         (None, _, _) => Err(EventExtractorError::PropertyNotFound("FN".to_string())),
         (_, None, _) => Err(EventExtractorError::PropertyNotFound("UID".to_string())),
         (Some(fn_prop), Some(uid_prop), Some(bday_prop)) => generate_events_for_years(
@@ -180,11 +612,126 @@ pub fn convert(
     }
 }
 
+/// Extracts the events of a contact together with the information they
+/// were generated from.
+///
+/// The iCalendar representation of the events is created by `convert`.
+pub fn extract_events(
+    contact: &VcardContact,
+    years: &[i32],
+    language: Option<&str>,
+    filter: &ContactFilter,
+    source: &Path,
+) -> Result<Vec<ExtractedEvent>, EventExtractorError> {
+    let events = convert(contact, years, language, filter)?;
+
+    if events.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let props = ContactProperties::try_from(contact)?;
+    let fn_prop = select_property(&props.fn_props.iter().collect::<Vec<_>>(), language)
+        .ok_or(EventExtractorError::PropertyNotFound("FN".into()))?;
+    let bday_prop = select_date(&props.bday_props.iter().collect::<Vec<_>>(), language)
+        .0
+        .ok_or(EventExtractorError::PropertyNotFound("BDAY".into()))?;
+    let date = ExtractedDate::try_from(bday_prop)?;
+    let contact_uid = props
+        .uid_prop
+        .and_then(|uid_prop| uid_prop.value)
+        .ok_or(EventExtractorError::PropertyValueNotFound("UID".into()))?;
+    let name = unescape_text(
+        fn_prop
+            .value
+            .as_ref()
+            .ok_or(EventExtractorError::PropertyValueNotFound("FN".into()))?,
+    );
+
+    years
+        .iter()
+        .zip(events)
+        .map(|(year, event)| {
+            Ok(ExtractedEvent {
+                uid: event
+                    .properties
+                    .iter()
+                    .find(|&elem| elem.name == "UID")
+                    .and_then(|elem| elem.value.clone())
+                    .ok_or(EventExtractorError::PropertyNotFound("UID".into()))?,
+                contact_uid: contact_uid.clone(),
+                name: name.clone(),
+                kind: EventKind::Birthday,
+                date: NaiveDate::from_ymd_opt(*year, date.month, date.day)
+                    .ok_or(EventExtractorError::UnexpectedDateFormat)?,
+                age: date.year.map(|date_year| year - date_year),
+                source: source.to_path_buf(),
+                event,
+            })
+        })
+        .collect()
+}
+
+/// Checks whether a contact has several birthdays with different dates.
+pub fn find_date_conflict(
+    contact: &VcardContact,
+    language: Option<&str>,
+    source: &Path,
+) -> Result<Option<Diagnostic>, EventExtractorError> {
+    let props = ContactProperties::try_from(contact)?;
+    let (selected, conflicts) = select_date(&props.bday_props.iter().collect::<Vec<_>>(), language);
+
+    match (selected, conflicts.is_empty()) {
+        (Some(selected), false) => Ok(Some(Diagnostic::DateConflict {
+            source: source.to_path_buf(),
+            uid: props
+                .uid_prop
+                .and_then(|uid_prop| uid_prop.value)
+                .unwrap_or_default(),
+            selected: selected.value.clone().unwrap_or_default(),
+            others: conflicts
+                .iter()
+                .map(|conflict| conflict.value.clone().unwrap_or_default())
+                .collect(),
+        })),
+        (_, _) => Ok(None),
+    }
+}
+
+/// Decoded properties of a contact relevant for the event extraction.
+struct ContactProperties {
+    fn_props: Vec<Property>,
+    bday_props: Vec<Property>,
+    uid_prop: Option<Property>,
+}
+
+impl TryFrom<&VcardContact> for ContactProperties {
+    type Error = EventExtractorError;
+
+    fn try_from(contact: &VcardContact) -> Result<Self, Self::Error> {
+        let mut props = ContactProperties {
+            fn_props: Vec::new(),
+            bday_props: Vec::new(),
+            uid_prop: None,
+        };
+
+        for prop in &contact.properties {
+            match prop.name.as_str() {
+                "FN" => props.fn_props.push(decode_property(prop)?),
+                "BDAY" => props.bday_props.push(decode_property(prop)?),
+                "UID" => props.uid_prop = Some(decode_property(prop)?),
+                _ => {}
+            }
+        }
+
+        Ok(props)
+    }
+}
+
 fn generate_events_for_years(
     fn_prop: &Property,
     uid_prop: &Property,
     date: &ExtractedDate,
-    years: &Vec<i32>,
+    years: &[i32],
     timestamp: &DateTime<Utc>,
 ) -> Result<Vec<IcalEvent>, EventExtractorError> {
     let uid = uid_prop
@@ -241,7 +788,7 @@ fn generate_events_for_years(
                 },
                 Property {
                     name: "SUMMARY".into(),
-                    params: fn_prop.params.clone(),
+                    params: map_text_params(fn_prop.params.as_ref()),
                     value: Some(match date.year {
                         Some(date_year) => format!("Birthday: {} ({})", fn_value, year - date_year),
                         None => format!("Birthday: {}", fn_value),
@@ -254,6 +801,17 @@ fn generate_events_for_years(
         .collect()
 }
 
+pub(crate) fn find_param<'a>(property: &'a Property, key: &str) -> Option<&'a str> {
+    property
+        .params
+        .as_ref()?
+        .iter()
+        .find(|(param_key, _)| param_key == key)?
+        .1
+        .first()
+        .map(|value| value.as_str())
+}
+
 impl TryFrom<&Property> for ExtractedDate {
     type Error = EventExtractorError;
 
@@ -276,7 +834,7 @@ impl TryFrom<&Property> for ExtractedDate {
 
                 let param_value =
                     param_values
-                        .get(0)
+                        .first()
                         .ok_or(EventExtractorError::DateExtractionFailed(
                             "value type not found".to_string(),
                         ))?;
@@ -403,7 +961,7 @@ mod tests {
                         month: 10,
                         day: 5
                     },
-                    &vec![2000, 2001],
+                    &[2000, 2001],
                     &timestamp
                 )
                 .unwrap()
@@ -519,7 +1077,7 @@ mod tests {
                         month: 10,
                         day: 5
                     },
-                    &vec![2012, 2013],
+                    &[2012, 2013],
                     &timestamp
                 )
                 .unwrap()
@@ -611,4 +1169,55 @@ mod tests {
             )
         )
     }
+
+    #[test]
+    fn convert_1() {
+        let events = convert(
+            &VcardContact {
+                properties: vec![
+                    Property {
+                        name: "FN".into(),
+                        params: Some(vec![
+                            ("ENCODING".into(), vec!["QUOTED-PRINTABLE".into()]),
+                            ("CHARSET".into(), vec!["UTF-8".into()]),
+                        ]),
+                        value: Some("=C3=9Cmit".into()),
+                    },
+                    Property {
+                        name: "UID".into(),
+                        params: None,
+                        value: Some("test_uid".into()),
+                    },
+                    Property {
+                        name: "BDAY".into(),
+                        params: Some(vec![("VALUE".into(), vec!["DATE".into()])]),
+                        value: Some("--1005".into()),
+                    },
+                ],
+            },
+            &[2000],
+            None,
+            &ContactFilter::default(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            format!(
+                "{:?}",
+                events[0]
+                    .properties
+                    .iter()
+                    .find(|&elem| elem.name == "SUMMARY")
+                    .unwrap()
+            ),
+            format!(
+                "{:?}",
+                Property {
+                    name: "SUMMARY".into(),
+                    params: None,
+                    value: Some("Birthday: Ümit".into()),
+                }
+            )
+        )
+    }
 }
@@ -0,0 +1,85 @@
+#[cfg(test)]
+mod tests;
+
+use std::{
+    fs::{File, OpenOptions, TryLockError},
+    path::{Path, PathBuf},
+};
+
+use thiserror::Error;
+
+use crate::{
+    config::{Entry, LockBehavior},
+    dav::is_collection_url,
+    writes_event_files, STDOUT,
+};
+
+/// Name of the lock file in the output directory.
+pub const LOCK_FILE: &str = ".event-extractor.lock";
+
+#[derive(Error, Debug)]
+pub enum LockError {
+    #[error("\"{}\" is locked by another run", .0.to_string_lossy())]
+    Locked(PathBuf),
+    #[error("std::io error")]
+    StdIoError(#[from] std::io::Error),
+}
+
+/// Advisory lock on the output directory of a configuration entry, released
+/// when dropped.
+#[derive(Debug)]
+pub struct OutputLock {
+    _file: Option<File>,
+}
+
+/// Path of the lock file of a configuration entry, or `None` if the output
+/// is not a local file or directory.
+pub fn lock_path(config_entry: &Entry) -> Option<PathBuf> {
+    if config_entry.output == STDOUT || is_collection_url(&config_entry.output) {
+        return None;
+    }
+
+    let output = Path::new(&config_entry.output);
+    let directory = match writes_event_files(config_entry) {
+        true => output,
+        false => output
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+            .unwrap_or(Path::new(".")),
+    };
+
+    Some(directory.join(LOCK_FILE))
+}
+
+/// Locks the output directory of a configuration entry.
+///
+/// If it is locked by another run, the entry's `lock` behaviour decides
+/// whether to wait for it, to skip the entry (`Ok(None)`) or to fail.
+pub fn lock_output(config_entry: &Entry) -> Result<Option<OutputLock>, LockError> {
+    let Some(path) = lock_path(config_entry) else {
+        return Ok(Some(OutputLock { _file: None }));
+    };
+    let file = OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(&path)?;
+
+    match file.try_lock() {
+        Ok(()) => {}
+        Err(TryLockError::WouldBlock) => match config_entry.lock {
+            LockBehavior::Wait => {
+                log::info!(
+                    "\"{}\" is locked by another run, waiting",
+                    path.to_string_lossy()
+                );
+                file.lock()?;
+            }
+            LockBehavior::Skip => return Ok(None),
+            LockBehavior::Fail => return Err(LockError::Locked(path)),
+        },
+        Err(TryLockError::Error(e)) => return Err(e.into()),
+    }
+
+    Ok(Some(OutputLock { _file: Some(file) }))
+}
@@ -0,0 +1,99 @@
+use std::{thread, time::Duration};
+
+use crate::{config::OutputFormat, process_entry, report::Diagnostic};
+
+use super::*;
+
+fn entry(output: &Path, lock: LockBehavior) -> Entry {
+    Entry {
+        input: output.join("missing").to_string_lossy().into(),
+        output: output.to_string_lossy().into(),
+        lock,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn lock_path_1() {
+    assert_eq!(
+        lock_path(&entry(Path::new("/path/output"), LockBehavior::Wait)),
+        Some(PathBuf::from("/path/output/.event-extractor.lock"))
+    );
+    assert_eq!(
+        lock_path(&Entry {
+            output: "/path/events.csv".into(),
+            output_format: OutputFormat::Csv,
+            ..Default::default()
+        }),
+        Some(PathBuf::from("/path/.event-extractor.lock"))
+    );
+    assert_eq!(
+        lock_path(&Entry {
+            output: "events.csv".into(),
+            output_format: OutputFormat::Csv,
+            ..Default::default()
+        }),
+        Some(PathBuf::from("./.event-extractor.lock"))
+    );
+    assert_eq!(
+        lock_path(&Entry {
+            output: "-".into(),
+            output_format: OutputFormat::Csv,
+            ..Default::default()
+        }),
+        None
+    );
+    assert_eq!(
+        lock_path(&entry(
+            Path::new("https://example.com/calendar/"),
+            LockBehavior::Wait
+        )),
+        None
+    );
+}
+
+#[test]
+fn lock_output_1() {
+    let output = tempfile::tempdir().unwrap();
+    let lock = lock_output(&entry(output.path(), LockBehavior::Fail))
+        .unwrap()
+        .unwrap();
+
+    assert!(lock_output(&entry(output.path(), LockBehavior::Skip))
+        .unwrap()
+        .is_none());
+    assert!(matches!(
+        lock_output(&entry(output.path(), LockBehavior::Fail)),
+        Err(LockError::Locked(_))
+    ));
+
+    let report = process_entry(&entry(output.path(), LockBehavior::Skip)).unwrap();
+    assert_eq!(
+        report.diagnostics,
+        vec![Diagnostic::OutputLocked {
+            path: output.path().to_path_buf()
+        }]
+    );
+
+    drop(lock);
+    assert!(lock_output(&entry(output.path(), LockBehavior::Fail))
+        .unwrap()
+        .is_some());
+}
+
+#[test]
+fn lock_output_2() {
+    let output = tempfile::tempdir().unwrap();
+    let lock = lock_output(&entry(output.path(), LockBehavior::Wait))
+        .unwrap()
+        .unwrap();
+    let release = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(100));
+        drop(lock);
+    });
+
+    assert!(lock_output(&entry(output.path(), LockBehavior::Wait))
+        .unwrap()
+        .is_some());
+    release.join().unwrap();
+}
@@ -1,26 +1,198 @@
-use clap::Parser;
-use event_extractor::{self, config::Config, process_entry};
+use std::time::Duration;
+
+use chrono::Local;
+use clap::{Args, Parser, Subcommand};
+use event_extractor::{
+    self,
+    config::{Config, ConfigError, Entry},
+    daemon::daemon,
+    event_years, extract_merged, merge_entries, process_merged,
+    serve::serve,
+    upcoming::{format_table, upcoming_events},
+    watch::watch,
+};
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
-struct Args {
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+    #[command(flatten)]
+    run: RunArgs,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Extract events into the output (default)
+    Run(RunArgs),
+    /// List the events of the next days
+    Upcoming(UpcomingArgs),
+    /// Serve the calendars for webcal subscriptions
+    Serve(ServeArgs),
+    /// Keep running and regenerate the events periodically
+    Daemon(DaemonArgs),
+}
+
+#[derive(Args, Debug)]
+struct InputArgs {
     /// Configuration file
+    #[arg(
+        short,
+        long,
+        required_unless_present = "input",
+        conflicts_with = "input"
+    )]
+    config: Option<String>,
+    /// Input directory, contacts file or "-" for the standard input
     #[arg(short, long)]
-    config: String,
+    input: Option<String>,
+}
+
+#[derive(Args, Debug)]
+struct RunArgs {
+    #[command(flatten)]
+    input: InputArgs,
+    /// Output directory
+    #[arg(short, long, requires = "input")]
+    output: Option<String>,
+    /// Remove existing ics files from the output directory
+    #[arg(short, long, requires = "input")]
+    remove_files: bool,
+    /// Keep running and regenerate the events when the input files change
+    #[arg(short, long)]
+    watch: bool,
+}
+
+#[derive(Args, Debug)]
+struct UpcomingArgs {
+    #[command(flatten)]
+    input: InputArgs,
+    /// Number of days to look ahead
+    #[arg(short, long, default_value_t = 14)]
+    days: i64,
+}
+
+#[derive(Args, Debug)]
+struct ServeArgs {
+    #[command(flatten)]
+    input: InputArgs,
+    /// Address to listen on
+    #[arg(short, long, default_value = "127.0.0.1:8080")]
+    bind: String,
+}
+
+#[derive(Args, Debug)]
+struct DaemonArgs {
+    #[command(flatten)]
+    input: InputArgs,
+    /// Output directory
+    #[arg(short, long, requires = "input")]
+    output: Option<String>,
+    /// Remove existing ics files from the output directory
+    #[arg(short, long, requires = "input")]
+    remove_files: bool,
+    /// Minutes between two runs
+    #[arg(long, default_value_t = 60, value_parser = clap::value_parser!(u64).range(1..))]
+    interval: u64,
 }
 
 fn main() -> anyhow::Result<()> {
     env_logger::init();
 
-    let args = Args::parse();
-    let config = Config::from_file(&args.config)?;
+    let cli = Cli::parse();
+
+    match cli.command {
+        Some(Command::Run(args)) => run(args),
+        Some(Command::Upcoming(args)) => upcoming(args),
+        Some(Command::Serve(args)) => serve_calendars(args),
+        Some(Command::Daemon(args)) => run_daemon(args),
+        None => run(cli.run),
+    }
+}
+
+fn load_config(
+    args: &InputArgs,
+    output: Option<&str>,
+    remove_files: bool,
+) -> Result<Config, ConfigError> {
+    match (&args.config, &args.input) {
+        (Some(config), _) => Config::from_file(config),
+        (None, Some(input)) => Ok(Config {
+            entries: vec![Entry {
+                input: input.clone(),
+                output: output.unwrap_or_default().to_string(),
+                remove_files,
+                ..Default::default()
+            }],
+        }),
+        (None, None) => unreachable!("arguments are validated by clap"),
+    }
+}
+
+fn run(args: RunArgs) -> anyhow::Result<()> {
+    if args.input.input.is_some() && args.output.is_none() {
+        anyhow::bail!("the argument \"--output\" is required with \"--input\"");
+    }
 
-    for entry in config.entries {
-        match process_entry(&entry) {
-            Ok(_) => {}
+    let config = load_config(&args.input, args.output.as_deref(), args.remove_files)?;
+
+    if args.watch {
+        return Ok(watch(&config.entries)?);
+    }
+
+    for entries in merge_entries(&config.entries) {
+        match process_merged(&entries) {
+            Ok(report) => log::info!("{}", report),
             Err(e) => log::error!("{}", e),
         }
     }
 
     Ok(())
 }
+
+fn upcoming(args: UpcomingArgs) -> anyhow::Result<()> {
+    let config = load_config(&args.input, None, false)?;
+    let today = Local::now().date_naive();
+    let years = event_years();
+    let mut events = Vec::new();
+
+    for entries in merge_entries(&config.entries) {
+        match extract_merged(&entries, &years) {
+            Ok((mut entry_events, _)) => events.append(&mut entry_events),
+            Err(e) => log::error!("{}", e),
+        }
+    }
+
+    print!(
+        "{}",
+        format_table(&upcoming_events(&events, today, args.days), today)
+    );
+
+    Ok(())
+}
+
+fn serve_calendars(args: ServeArgs) -> anyhow::Result<()> {
+    let config = load_config(&args.input, None, false)?;
+
+    for entry in &config.entries {
+        log::info!(
+            "serving \"{}\" as \"/calendars/{}.ics\"",
+            entry.input,
+            entry.calendar_name()
+        );
+    }
+
+    Ok(serve(&config.entries, &args.bind)?)
+}
+
+fn run_daemon(args: DaemonArgs) -> anyhow::Result<()> {
+    if args.input.input.is_some() && args.output.is_none() {
+        anyhow::bail!("the argument \"--output\" is required with \"--input\"");
+    }
+
+    Ok(daemon(
+        || load_config(&args.input, args.output.as_deref(), args.remove_files),
+        Duration::from_secs(args.interval * 60),
+    )?)
+}
@@ -0,0 +1,81 @@
+use std::time::Duration;
+
+use crate::{config::Entry, extract_entry, EventExtractorError};
+
+use super::*;
+
+#[test]
+fn map_parallel_1() {
+    let items: Vec<u64> = (0..20).collect();
+    let running = AtomicUsize::new(0);
+    let max_running = AtomicUsize::new(0);
+
+    let results = map_parallel(&items, 4, |item| {
+        let now_running = running.fetch_add(1, Ordering::SeqCst) + 1;
+
+        max_running.fetch_max(now_running, Ordering::SeqCst);
+        // later items finish earlier
+        thread::sleep(Duration::from_millis(20 - item));
+        running.fetch_sub(1, Ordering::SeqCst);
+        item * 2
+    });
+
+    assert_eq!(results, (0..20).map(|item| item * 2).collect::<Vec<_>>());
+    assert!(max_running.load(Ordering::SeqCst) <= 4);
+}
+
+#[test]
+fn map_parallel_2() {
+    let items = ["a", "b"];
+
+    assert_eq!(map_parallel(&items, 0, |item| item.len()), vec![1, 1]);
+    assert_eq!(map_parallel(&items, 8, |item| item.len()), vec![1, 1]);
+    assert!(map_parallel(&[] as &[&str], 8, |item| item.len()).is_empty());
+}
+
+#[test]
+fn extract_entry_1() {
+    let input = tempfile::tempdir().unwrap();
+    let entry = |jobs| Entry {
+        input: input.path().to_string_lossy().into(),
+        jobs: Some(jobs),
+        ..Default::default()
+    };
+
+    for index in 0..10 {
+        std::fs::write(
+            input.path().join(format!("{}.vcf", index)),
+            format!(
+                "BEGIN:VCARD\r\nVERSION:3.0\r\nUID:{}\r\nFN:Person {}\r\nBDAY;VALUE=DATE:19901005\r\nEND:VCARD\r\n",
+                index, index
+            ),
+        )
+        .unwrap();
+    }
+
+    let uids = |jobs| {
+        extract_entry(&entry(jobs), &[2023])
+            .unwrap()
+            .0
+            .into_iter()
+            .map(|event| event.uid)
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(uids(4), uids(1));
+
+    // the error of the first failing file is returned
+    std::fs::write(
+        input.path().join("3.vcf"),
+        "BEGIN:VCARD\r\nVERSION:3.0\r\nUID:3\r\nBDAY;VALUE=DATE:19901005\r\nEND:VCARD\r\n",
+    )
+    .unwrap();
+    std::fs::write(
+        input.path().join("7.vcf"),
+        "BEGIN:VCARD\r\nVERSION:3.0\r\nFN:Person 7\r\nBDAY;VALUE=DATE:19901005\r\nEND:VCARD\r\n",
+    )
+    .unwrap();
+    assert!(matches!(
+        extract_entry(&entry(4), &[2023]),
+        Err(EventExtractorError::PropertyNotFound(property)) if property == "FN"
+    ));
+}
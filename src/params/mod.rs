@@ -0,0 +1,33 @@
+#[cfg(test)]
+mod tests;
+
+type Params = Vec<(String, Vec<String>)>;
+
+/// Maps the parameters of a vCard property onto parameters valid for an
+/// iCalendar text property (e.g. "SUMMARY").
+///
+/// Parameters without an iCalendar equivalent are dropped.
+pub fn map_text_params(params: Option<&Params>) -> Option<Params> {
+    let mapped: Params = params?
+        .iter()
+        .filter_map(|(key, values)| map_text_param(key, values))
+        .collect();
+
+    (!mapped.is_empty()).then_some(mapped)
+}
+
+fn map_text_param(key: &str, values: &[String]) -> Option<(String, Vec<String>)> {
+    match key.to_uppercase().as_str() {
+        // RFC 6350 5.1 and RFC 5545 3.2.10 use the same language tags
+        "LANGUAGE" => values
+            .first()
+            .map(|value| ("LANGUAGE".into(), vec![value.clone()])),
+        // RFC 6350 5.2 - 5.10 and the vCard 2.1 "CHARSET" and "ENCODING"
+        // parameters only describe the vCard property
+        "VALUE" | "PREF" | "ALTID" | "PID" | "TYPE" | "MEDIATYPE" | "CALSCALE" | "SORT-AS"
+        | "GEO" | "TZ" | "LABEL" | "CHARSET" | "ENCODING" => None,
+        // experimental parameters are allowed on any iCalendar property
+        key if key.starts_with("X-") => Some((key.into(), values.to_vec())),
+        _ => None,
+    }
+}
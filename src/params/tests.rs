@@ -0,0 +1,106 @@
+use super::*;
+
+fn map_single(key: &str, values: &[&str]) -> Option<Params> {
+    map_text_params(Some(&vec![(
+        key.to_string(),
+        values.iter().map(|value| value.to_string()).collect(),
+    )]))
+}
+
+#[test]
+fn map_text_params_none() {
+    assert_eq!(map_text_params(None), None)
+}
+
+#[test]
+fn map_text_params_language() {
+    assert_eq!(
+        map_single("LANGUAGE", &["de-AT"]),
+        Some(vec![("LANGUAGE".into(), vec!["de-AT".into()])])
+    )
+}
+
+#[test]
+fn map_text_params_value() {
+    assert_eq!(map_single("VALUE", &["text"]), None)
+}
+
+#[test]
+fn map_text_params_pref() {
+    assert_eq!(map_single("PREF", &["1"]), None)
+}
+
+#[test]
+fn map_text_params_altid() {
+    assert_eq!(map_single("ALTID", &["1"]), None)
+}
+
+#[test]
+fn map_text_params_pid() {
+    assert_eq!(map_single("PID", &["1.1"]), None)
+}
+
+#[test]
+fn map_text_params_type() {
+    assert_eq!(map_single("TYPE", &["work", "home"]), None)
+}
+
+#[test]
+fn map_text_params_mediatype() {
+    assert_eq!(map_single("MEDIATYPE", &["text/plain"]), None)
+}
+
+#[test]
+fn map_text_params_calscale() {
+    assert_eq!(map_single("CALSCALE", &["gregorian"]), None)
+}
+
+#[test]
+fn map_text_params_sort_as() {
+    assert_eq!(map_single("SORT-AS", &["Person", "Test"]), None)
+}
+
+#[test]
+fn map_text_params_geo() {
+    assert_eq!(map_single("GEO", &["geo:12.3457,78.910"]), None)
+}
+
+#[test]
+fn map_text_params_tz() {
+    assert_eq!(map_single("TZ", &["Europe/Vienna"]), None)
+}
+
+#[test]
+fn map_text_params_label() {
+    assert_eq!(map_single("LABEL", &["Street 1"]), None)
+}
+
+#[test]
+fn map_text_params_charset() {
+    assert_eq!(map_single("CHARSET", &["UTF-8"]), None)
+}
+
+#[test]
+fn map_text_params_encoding() {
+    assert_eq!(map_single("ENCODING", &["QUOTED-PRINTABLE"]), None)
+}
+
+#[test]
+fn map_text_params_experimental() {
+    assert_eq!(
+        map_single("X-TEST", &["value"]),
+        Some(vec![("X-TEST".into(), vec!["value".into()])])
+    )
+}
+
+#[test]
+fn map_text_params_mixed() {
+    assert_eq!(
+        map_text_params(Some(&vec![
+            ("PID".into(), vec!["1.1".into()]),
+            ("LANGUAGE".into(), vec!["en".into()]),
+            ("ALTID".into(), vec!["1".into()]),
+        ])),
+        Some(vec![("LANGUAGE".into(), vec!["en".into()])])
+    )
+}
@@ -0,0 +1,170 @@
+#[cfg(test)]
+mod tests;
+
+use std::{fmt, path::PathBuf};
+
+use crate::filter::Group;
+
+/// Summary of processing a configuration entry.
+#[derive(Debug, Default, PartialEq)]
+pub struct Report {
+    pub contacts: usize,
+    pub events: usize,
+    pub diagnostics: Vec<Diagnostic>,
+    /// Contacts of the kind "group", which are found regardless of the
+    /// filters.
+    pub groups: Vec<Group>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Diagnostic {
+    /// A contact has several birthdays with different dates.
+    DateConflict {
+        source: PathBuf,
+        uid: String,
+        selected: String,
+        others: Vec<String>,
+    },
+    /// An input file or directory could not be read.
+    UnreadableInput { path: PathBuf, message: String },
+    /// A resource was changed on the server and was therefore not
+    /// overwritten or deleted.
+    UploadConflict { url: String },
+    /// The output was locked by another run and the entry was skipped.
+    OutputLocked { path: PathBuf },
+    /// A duplicate contact was merged into another contact, whose events
+    /// are kept.
+    MergedContact {
+        source: PathBuf,
+        uid: String,
+        kept_source: PathBuf,
+        kept_uid: String,
+    },
+}
+
+impl Report {
+    /// Adds the counts and diagnostics of another report.
+    pub fn merge(&mut self, other: Report) {
+        self.contacts += other.contacts;
+        self.events += other.events;
+        self.diagnostics.extend(other.diagnostics);
+        self.groups.extend(other.groups);
+    }
+
+    pub fn date_conflicts(&self) -> usize {
+        self.diagnostics
+            .iter()
+            .filter(|diagnostic| matches!(diagnostic, Diagnostic::DateConflict { .. }))
+            .count()
+    }
+
+    pub fn unreadable_inputs(&self) -> usize {
+        self.diagnostics
+            .iter()
+            .filter(|diagnostic| matches!(diagnostic, Diagnostic::UnreadableInput { .. }))
+            .count()
+    }
+
+    pub fn merged_contacts(&self) -> usize {
+        self.diagnostics
+            .iter()
+            .filter(|diagnostic| matches!(diagnostic, Diagnostic::MergedContact { .. }))
+            .count()
+    }
+
+    pub fn upload_conflicts(&self) -> usize {
+        self.diagnostics
+            .iter()
+            .filter(|diagnostic| matches!(diagnostic, Diagnostic::UploadConflict { .. }))
+            .count()
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "processed {} contacts, generated {} events",
+            self.contacts, self.events
+        )?;
+
+        if self.date_conflicts() > 0 {
+            write!(
+                f,
+                ", {} contacts with conflicting dates",
+                self.date_conflicts()
+            )?;
+        }
+
+        if self.unreadable_inputs() > 0 {
+            write!(f, ", {} unreadable inputs", self.unreadable_inputs())?;
+        }
+
+        if self.upload_conflicts() > 0 {
+            write!(f, ", {} upload conflicts", self.upload_conflicts())?;
+        }
+
+        if self.merged_contacts() > 0 {
+            write!(f, ", {} merged duplicates", self.merged_contacts())?;
+        }
+
+        if self
+            .diagnostics
+            .iter()
+            .any(|diagnostic| matches!(diagnostic, Diagnostic::OutputLocked { .. }))
+        {
+            write!(f, ", skipped because the output is locked")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Diagnostic::DateConflict {
+                source,
+                uid,
+                selected,
+                others,
+            } => write!(
+                f,
+                "date conflict in \"{}\" for contact \"{}\": using \"{}\", ignoring \"{}\"",
+                source.to_string_lossy(),
+                uid,
+                selected,
+                others.join("\", \"")
+            ),
+            Diagnostic::UnreadableInput { path, message } => write!(
+                f,
+                "could not read \"{}\": {}",
+                path.to_string_lossy(),
+                message
+            ),
+            Diagnostic::UploadConflict { url } => write!(
+                f,
+                "\"{}\" was changed on the server and was left unchanged",
+                url
+            ),
+            Diagnostic::MergedContact {
+                source,
+                uid,
+                kept_source,
+                kept_uid,
+            } => write!(
+                f,
+                "merged contact \"{}\" of \"{}\" into \"{}\" of \"{}\"",
+                uid,
+                source.to_string_lossy(),
+                kept_uid,
+                kept_source.to_string_lossy()
+            ),
+            Diagnostic::OutputLocked { path } => write!(
+                f,
+                "\"{}\" is locked by another run, skipping",
+                path.to_string_lossy()
+            ),
+        }
+    }
+}
@@ -0,0 +1,71 @@
+use super::*;
+
+#[test]
+fn report_to_string_1() {
+    assert_eq!(
+        Report {
+            contacts: 3,
+            events: 8,
+            diagnostics: vec![
+                Diagnostic::DateConflict {
+                    source: "/path/input/test.vcf".into(),
+                    uid: "test_uid".into(),
+                    selected: "19901005".into(),
+                    others: vec!["19901006".into()]
+                },
+                Diagnostic::UnreadableInput {
+                    path: "/path/input/other.vcf".into(),
+                    message: "permission denied".into()
+                },
+                Diagnostic::UploadConflict {
+                    url: "http://localhost/calendars/test_uid_bday_2023.ics".into()
+                }
+            ],
+            groups: Vec::new()
+        }
+        .to_string(),
+        "processed 3 contacts, generated 8 events, 1 contacts with conflicting dates, 1 unreadable inputs, 1 upload conflicts"
+    )
+}
+
+#[test]
+fn diagnostic_to_string_1() {
+    assert_eq!(
+        Diagnostic::DateConflict {
+            source: "/path/input/test.vcf".into(),
+            uid: "test_uid".into(),
+            selected: "19901005".into(),
+            others: vec!["19901006".into(), "--1007".into()]
+        }
+        .to_string(),
+        "date conflict in \"/path/input/test.vcf\" for contact \"test_uid\": using \"19901005\", ignoring \"19901006\", \"--1007\""
+    )
+}
+
+#[test]
+fn report_to_string_2() {
+    assert_eq!(
+        Report {
+            diagnostics: vec![Diagnostic::OutputLocked {
+                path: "/path/output".into()
+            }],
+            ..Default::default()
+        }
+        .to_string(),
+        "processed 0 contacts, generated 0 events, skipped because the output is locked"
+    )
+}
+
+#[test]
+fn diagnostic_to_string_2() {
+    assert_eq!(
+        Diagnostic::MergedContact {
+            source: "/path/work/jane.vcf".into(),
+            uid: "jane-work".into(),
+            kept_source: "/path/home/jane.vcf".into(),
+            kept_uid: "jane".into()
+        }
+        .to_string(),
+        "merged contact \"jane-work\" of \"/path/work/jane.vcf\" into \"jane\" of \"/path/home/jane.vcf\""
+    )
+}
@@ -0,0 +1,144 @@
+#[cfg(test)]
+mod tests;
+
+use ical::property::Property;
+
+use crate::{find_param, ExtractedDate};
+
+/// Selects the most suitable property from a list of alternatives.
+///
+/// Properties are ranked by how well their "LANGUAGE" parameter matches the
+/// preferred language and then by their "PREF" parameter. Among equally
+/// ranked properties, the first one is selected.
+pub fn select_property<'a>(
+    properties: &[&'a Property],
+    language: Option<&str>,
+) -> Option<&'a Property> {
+    properties
+        .iter()
+        .enumerate()
+        .min_by_key(|(index, property)| {
+            (
+                language_rank(property, language),
+                pref_rank(property),
+                *index,
+            )
+        })
+        .map(|(_, property)| *property)
+}
+
+/// Collapses properties sharing the same "ALTID" parameter into the most
+/// suitable representative of each group.
+///
+/// Properties without an "ALTID" parameter form a group of their own. The
+/// order of the groups follows their first appearance.
+pub fn collapse_alternatives<'a>(
+    properties: &[&'a Property],
+    language: Option<&str>,
+) -> Vec<&'a Property> {
+    let mut groups: Vec<(Option<&str>, Vec<&'a Property>)> = Vec::new();
+
+    for &property in properties {
+        match find_param(property, "ALTID") {
+            Some(altid) => match groups
+                .iter_mut()
+                .find(|(group_altid, _)| *group_altid == Some(altid))
+            {
+                Some((_, group)) => group.push(property),
+                None => groups.push((Some(altid), vec![property])),
+            },
+            None => groups.push((None, vec![property])),
+        }
+    }
+
+    groups
+        .iter()
+        .filter_map(|(_, group)| {
+            // textual alternatives (e.g. "circa 1800") cannot be used as dates
+            let dates: Vec<&Property> = group
+                .iter()
+                .filter(|property| {
+                    !find_param(property, "VALUE")
+                        .is_some_and(|value| value.eq_ignore_ascii_case("text"))
+                })
+                .copied()
+                .collect();
+
+            if dates.is_empty() {
+                select_property(group, language)
+            } else {
+                select_property(&dates, language)
+            }
+        })
+        .collect()
+}
+
+/// Selects the most suitable date from a list of date properties.
+///
+/// Alternatives sharing an "ALTID" parameter are collapsed first. The
+/// remaining properties with a date different from the selected one are
+/// returned as conflicts.
+pub fn select_date<'a>(
+    properties: &[&'a Property],
+    language: Option<&str>,
+) -> (Option<&'a Property>, Vec<&'a Property>) {
+    let candidates = collapse_alternatives(properties, language);
+    let selected = select_property(&candidates, language);
+    let mut conflicts: Vec<&Property> = Vec::new();
+
+    if let Some(date) = selected.and_then(|property| ExtractedDate::try_from(property).ok()) {
+        for &candidate in &candidates {
+            let differs = ExtractedDate::try_from(candidate).is_ok_and(|other| other != date);
+
+            if differs
+                && !conflicts
+                    .iter()
+                    .any(|conflict| conflict.value == candidate.value)
+            {
+                conflicts.push(candidate);
+            }
+        }
+    }
+
+    (selected, conflicts)
+}
+
+fn language_rank(property: &Property, language: Option<&str>) -> u8 {
+    let language = match language {
+        Some(language) => language,
+        None => return 0,
+    };
+
+    match find_param(property, "LANGUAGE") {
+        Some(value) if value.eq_ignore_ascii_case(language) => 0,
+        Some(value) if primary_subtag(value).eq_ignore_ascii_case(primary_subtag(language)) => 1,
+        None => 2,
+        Some(_) => 3,
+    }
+}
+
+fn pref_rank(property: &Property) -> u8 {
+    if let Some(pref) = find_param(property, "PREF").and_then(|pref| pref.parse::<u8>().ok()) {
+        return pref;
+    }
+
+    // vCard 3.0 marks preferred properties with "TYPE=pref"
+    let type_pref = property.params.as_ref().is_some_and(|params| {
+        params.iter().any(|(key, values)| {
+            key == "TYPE"
+                && values
+                    .iter()
+                    .any(|value| value.eq_ignore_ascii_case("pref"))
+        })
+    });
+
+    if type_pref {
+        1
+    } else {
+        101
+    }
+}
+
+fn primary_subtag(language: &str) -> &str {
+    language.split('-').next().unwrap_or(language)
+}
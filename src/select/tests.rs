@@ -0,0 +1,86 @@
+use super::*;
+
+fn property(value: &str, params: Vec<(&str, &str)>) -> Property {
+    Property {
+        name: "FN".into(),
+        params: if params.is_empty() {
+            None
+        } else {
+            Some(
+                params
+                    .iter()
+                    .map(|(key, value)| (key.to_string(), vec![value.to_string()]))
+                    .collect(),
+            )
+        },
+        value: Some(value.into()),
+    }
+}
+
+#[test]
+fn select_property_language() {
+    let properties = [
+        property("Test Person", vec![("ALTID", "1"), ("LANGUAGE", "en")]),
+        property("Testperson", vec![("ALTID", "1"), ("LANGUAGE", "de")]),
+    ];
+
+    assert_eq!(
+        select_property(&properties.iter().collect::<Vec<_>>(), Some("de-AT"))
+            .unwrap()
+            .value,
+        Some("Testperson".into())
+    )
+}
+
+#[test]
+fn select_property_pref() {
+    let properties = [
+        property("Test Person", vec![]),
+        property("Dr. Test Person", vec![("PREF", "1")]),
+        property("T. Person", vec![("TYPE", "pref")]),
+    ];
+
+    assert_eq!(
+        select_property(&properties.iter().collect::<Vec<_>>(), None)
+            .unwrap()
+            .value,
+        Some("Dr. Test Person".into())
+    )
+}
+
+#[test]
+fn collapse_alternatives_1() {
+    let properties = [
+        property("circa 1800", vec![("ALTID", "1"), ("VALUE", "text")]),
+        property("18000101", vec![("ALTID", "1"), ("VALUE", "DATE")]),
+        property("--0214", vec![("VALUE", "DATE")]),
+    ];
+
+    assert_eq!(
+        collapse_alternatives(&properties.iter().collect::<Vec<_>>(), None)
+            .iter()
+            .map(|property| property.value.clone().unwrap())
+            .collect::<Vec<String>>(),
+        vec!["18000101".to_string(), "--0214".to_string()]
+    )
+}
+
+#[test]
+fn select_date_1() {
+    let properties = [
+        property("19901005", vec![("VALUE", "DATE")]),
+        property("19901006", vec![("VALUE", "DATE"), ("PREF", "1")]),
+        property("--1006", vec![("VALUE", "DATE"), ("PREF", "2")]),
+        property("19901006", vec![("VALUE", "DATE")]),
+    ];
+    let (selected, conflicts) = select_date(&properties.iter().collect::<Vec<_>>(), None);
+
+    assert_eq!(selected.unwrap().value, Some("19901006".into()));
+    assert_eq!(
+        conflicts
+            .iter()
+            .map(|property| property.value.clone().unwrap())
+            .collect::<Vec<String>>(),
+        vec!["19901005".to_string(), "--1006".to_string()]
+    )
+}
@@ -5,93 +5,348 @@ use ical::{
     parser::ical::component::{IcalCalendar, IcalEvent},
     property::Property,
 };
+use serde_json::{json, Map, Value};
 use thiserror::Error;
 
+use crate::config::OutputFormat;
+
+const XCAL_NAMESPACE: &str = "urn:ietf:params:xml:ns:icalendar-2.0";
+
 #[derive(Error, Debug)]
 pub enum SerializationError {
     #[error("serialization of property \"{}\" not implemented", .0)]
     SerializationNotImplemented(String),
 }
 
+/// Serializes a calendar in the given output format.
+pub fn serialize_calendar(
+    calendar: &IcalCalendar,
+    format: OutputFormat,
+) -> Result<String, SerializationError> {
+    match format {
+        OutputFormat::ICalendar => calendar_to_string(calendar),
+        OutputFormat::JCal => calendar_to_jcal(calendar),
+        OutputFormat::XCal => calendar_to_xcal(calendar),
+        OutputFormat::Csv | OutputFormat::JsonLines | OutputFormat::Html | OutputFormat::Atom => {
+            Err(SerializationError::SerializationNotImplemented(format!(
+                "{:?}",
+                format
+            )))
+        }
+    }
+}
+
 pub fn calendar_to_string(calendar: &IcalCalendar) -> Result<String, SerializationError> {
-    if calendar.alarms.len() > 0 {
+    check_calendar(calendar)?;
+
+    Ok(format!(
+        "BEGIN:VCALENDAR\r\n{}{}END:VCALENDAR\r\n",
+        calendar
+            .properties
+            .iter()
+            .map(property_to_string)
+            .collect::<Vec<String>>()
+            .join(""),
+        calendar
+            .events
+            .iter()
+            .map(event_to_string)
+            .collect::<Result<Vec<String>, SerializationError>>()?
+            .join("")
+    ))
+}
+
+fn check_calendar(calendar: &IcalCalendar) -> Result<(), SerializationError> {
+    if !calendar.alarms.is_empty() {
         return Err(SerializationError::SerializationNotImplemented(
             "calendar.alarms".into(),
         ));
     }
 
-    if calendar.free_busys.len() > 0 {
+    if !calendar.free_busys.is_empty() {
         return Err(SerializationError::SerializationNotImplemented(
             "calendar.free_busys".into(),
         ));
     }
 
-    if calendar.journals.len() > 0 {
+    if !calendar.journals.is_empty() {
         return Err(SerializationError::SerializationNotImplemented(
             "calendar.journals".into(),
         ));
     }
 
-    if calendar.timezones.len() > 0 {
+    if !calendar.timezones.is_empty() {
         return Err(SerializationError::SerializationNotImplemented(
             "calendar.timezones".into(),
         ));
     }
 
-    if calendar.todos.len() > 0 {
+    if !calendar.todos.is_empty() {
         return Err(SerializationError::SerializationNotImplemented(
             "calendar.todos".into(),
         ));
     }
 
+    Ok(())
+}
+
+pub fn event_to_string(event: &IcalEvent) -> Result<String, SerializationError> {
+    if !event.alarms.is_empty() {
+        return Err(SerializationError::SerializationNotImplemented(
+            "event.alarms".into(),
+        ));
+    }
+
     Ok(format!(
-        "BEGIN:VCALENDAR\r\n{}{}END:VCALENDAR\r\n",
+        "BEGIN:VEVENT\r\n{}END:VEVENT\r\n",
+        event
+            .properties
+            .iter()
+            .map(property_to_string)
+            .collect::<Vec<String>>()
+            .join("")
+    ))
+}
+
+pub fn property_to_string(property: &Property) -> String {
+    let mut out = property.name.to_owned();
+
+    if let Some(params) = &property.params {
+        for param in params {
+            out += &format!(";{}={}", param.0, param.1.join(","));
+        }
+    }
+
+    if let Some(value) = &property.value {
+        out += &format!(":{}", value);
+    }
+
+    out += "\r\n";
+    out
+}
+
+/// Serializes a calendar as jCal (RFC 7265).
+pub fn calendar_to_jcal(calendar: &IcalCalendar) -> Result<String, SerializationError> {
+    check_calendar(calendar)?;
+
+    let events = calendar
+        .events
+        .iter()
+        .map(event_to_jcal)
+        .collect::<Result<Vec<Value>, SerializationError>>()?;
+
+    Ok(json!([
+        "vcalendar",
+        calendar
+            .properties
+            .iter()
+            .map(property_to_jcal)
+            .collect::<Vec<Value>>(),
+        events
+    ])
+    .to_string())
+}
+
+pub fn event_to_jcal(event: &IcalEvent) -> Result<Value, SerializationError> {
+    if !event.alarms.is_empty() {
+        return Err(SerializationError::SerializationNotImplemented(
+            "event.alarms".into(),
+        ));
+    }
+
+    Ok(json!([
+        "vevent",
+        event
+            .properties
+            .iter()
+            .map(property_to_jcal)
+            .collect::<Vec<Value>>(),
+        []
+    ]))
+}
+
+pub fn property_to_jcal(property: &Property) -> Value {
+    let value_type = value_type(property);
+    let mut params = Map::new();
+
+    for (key, values) in property.params.iter().flatten() {
+        if key == "VALUE" {
+            continue;
+        }
+
+        params.insert(
+            key.to_lowercase(),
+            match values.as_slice() {
+                [value] => Value::String(value.clone()),
+                values => json!(values),
+            },
+        );
+    }
+
+    json!([
+        property.name.to_lowercase(),
+        params,
+        value_type,
+        structured_value(value_type, property.value.as_deref().unwrap_or_default())
+    ])
+}
+
+/// Serializes a calendar as xCal (RFC 6321).
+pub fn calendar_to_xcal(calendar: &IcalCalendar) -> Result<String, SerializationError> {
+    check_calendar(calendar)?;
+
+    Ok(format!(
+        "<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<icalendar xmlns=\"{}\"><vcalendar><properties>{}</properties><components>{}</components></vcalendar></icalendar>\n",
+        XCAL_NAMESPACE,
         calendar
             .properties
             .iter()
-            .map(|property| property_to_string(property))
+            .map(property_to_xcal)
             .collect::<Vec<String>>()
             .join(""),
         calendar
             .events
             .iter()
-            .map(|event| event_to_string(event))
+            .map(event_to_xcal)
             .collect::<Result<Vec<String>, SerializationError>>()?
             .join("")
     ))
 }
 
-pub fn event_to_string(event: &IcalEvent) -> Result<String, SerializationError> {
-    if event.alarms.len() > 0 {
+pub fn event_to_xcal(event: &IcalEvent) -> Result<String, SerializationError> {
+    if !event.alarms.is_empty() {
         return Err(SerializationError::SerializationNotImplemented(
             "event.alarms".into(),
         ));
     }
 
     Ok(format!(
-        "BEGIN:VEVENT\r\n{}END:VEVENT\r\n",
+        "<vevent><properties>{}</properties></vevent>",
         event
             .properties
             .iter()
-            .map(|property| property_to_string(&property))
+            .map(property_to_xcal)
             .collect::<Vec<String>>()
             .join("")
     ))
 }
 
-pub fn property_to_string(property: &Property) -> String {
-    let mut out = property.name.to_owned();
+pub fn property_to_xcal(property: &Property) -> String {
+    let name = property.name.to_lowercase();
+    let value_type = value_type(property);
+    let mut out = format!("<{}>", name);
+    let params: Vec<&(String, Vec<String>)> = property
+        .params
+        .iter()
+        .flatten()
+        .filter(|(key, _)| key != "VALUE")
+        .collect();
 
-    if let Some(params) = &property.params {
-        for param in params {
-            out += &format!(";{}={}", param.0, param.1.join(","));
+    if !params.is_empty() {
+        out += "<parameters>";
+
+        for (key, values) in params {
+            let key = key.to_lowercase();
+
+            out += &format!("<{}>", key);
+            for value in values {
+                out += &format!("<text>{}</text>", escape_xml(value));
+            }
+            out += &format!("</{}>", key);
         }
+
+        out += "</parameters>";
     }
 
-    if let Some(value) = &property.value {
-        out += &format!(":{}", value);
+    let value = match structured_value(value_type, property.value.as_deref().unwrap_or_default()) {
+        Value::String(value) => value,
+        value => value.to_string(),
+    };
+
+    out += &format!(
+        "<{}>{}</{}></{}>",
+        value_type,
+        escape_xml(&value),
+        value_type,
+        name
+    );
+    out
+}
+
+/// Determines the value type of a property from its "VALUE" parameter or,
+/// if it is not given, from the default value type of the property.
+fn value_type(property: &Property) -> &'static str {
+    let value_param = property
+        .params
+        .iter()
+        .flatten()
+        .find(|(key, _)| key == "VALUE")
+        .and_then(|(_, values)| values.first());
+
+    match value_param.map(|value| value.to_uppercase()).as_deref() {
+        Some("DATE") => "date",
+        Some("DATE-TIME") => "date-time",
+        Some("TEXT") => "text",
+        Some("URI") => "uri",
+        Some(_) => "unknown",
+        None => match property.name.as_str() {
+            "DTSTAMP" | "DTSTART" | "DTEND" | "CREATED" | "LAST-MODIFIED" | "RECURRENCE-ID" => {
+                "date-time"
+            }
+            "UID" | "SUMMARY" | "DESCRIPTION" | "LOCATION" | "STATUS" | "TRANSP" | "CLASS"
+            | "PRODID" | "VERSION" | "CALSCALE" | "METHOD" => "text",
+            _ => "unknown",
+        },
+    }
+}
+
+/// Converts an iCalendar value into the representation used by jCal and
+/// xCal, i.e. text is unescaped and dates use the extended format.
+fn structured_value(value_type: &str, value: &str) -> Value {
+    match value_type {
+        "text" => Value::String(unescape_text(value)),
+        "date" if value.len() == 8 && value.is_ascii() => Value::String(format!(
+            "{}-{}-{}",
+            &value[0..4],
+            &value[4..6],
+            &value[6..8]
+        )),
+        "date-time" if value.len() >= 15 && value.is_ascii() => Value::String(format!(
+            "{}-{}-{}T{}:{}:{}{}",
+            &value[0..4],
+            &value[4..6],
+            &value[6..8],
+            &value[9..11],
+            &value[11..13],
+            &value[13..15],
+            &value[15..]
+        )),
+        _ => Value::String(value.to_string()),
+    }
+}
+
+pub(crate) fn unescape_text(value: &str) -> String {
+    let mut out = String::new();
+    let mut chars = value.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some('n') | Some('N') => out.push('\n'),
+                Some(escaped) => out.push(escaped),
+                None => out.push('\\'),
+            },
+            c => out.push(c),
+        }
     }
 
-    out += "\r\n";
     out
 }
+
+pub(crate) fn escape_xml(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -68,3 +68,78 @@ fn property_to_string_1() {
         "test prop;param1=param1_1,param1_2;param2=param2_1:value1\r\n"
     )
 }
+
+fn test_calendar() -> IcalCalendar {
+    IcalCalendar {
+        properties: vec![Property {
+            name: "VERSION".into(),
+            value: Some("2.0".into()),
+            ..Default::default()
+        }],
+        events: vec![IcalEvent {
+            properties: vec![
+                Property {
+                    name: "UID".into(),
+                    value: Some("test_uid".into()),
+                    ..Default::default()
+                },
+                Property {
+                    name: "DTSTAMP".into(),
+                    value: Some("20230801T120000Z".into()),
+                    ..Default::default()
+                },
+                Property {
+                    name: "DTSTART".into(),
+                    params: Some(vec![("VALUE".into(), vec!["DATE".into()])]),
+                    value: Some("20001005".into()),
+                },
+                Property {
+                    name: "SUMMARY".into(),
+                    params: Some(vec![("LANGUAGE".into(), vec!["de".into()])]),
+                    value: Some("Birthday: Doe\\, J. <J&D>".into()),
+                },
+            ],
+            alarms: Vec::new(),
+        }],
+        ..Default::default()
+    }
+}
+
+#[test]
+fn calendar_to_jcal_1() {
+    assert_eq!(
+        serde_json::from_str::<Value>(&calendar_to_jcal(&test_calendar()).unwrap()).unwrap(),
+        json!([
+            "vcalendar",
+            [["version", {}, "text", "2.0"]],
+            [[
+                "vevent",
+                [
+                    ["uid", {}, "text", "test_uid"],
+                    ["dtstamp", {}, "date-time", "2023-08-01T12:00:00Z"],
+                    ["dtstart", {}, "date", "2000-10-05"],
+                    ["summary", {"language": "de"}, "text", "Birthday: Doe, J. <J&D>"]
+                ],
+                []
+            ]]
+        ])
+    )
+}
+
+#[test]
+fn calendar_to_xcal_1() {
+    assert_eq!(
+        calendar_to_xcal(&test_calendar()).unwrap(),
+        "<?xml version=\"1.0\" encoding=\"utf-8\"?>\n\
+         <icalendar xmlns=\"urn:ietf:params:xml:ns:icalendar-2.0\"><vcalendar>\
+         <properties><version><text>2.0</text></version></properties>\
+         <components><vevent><properties>\
+         <uid><text>test_uid</text></uid>\
+         <dtstamp><date-time>2023-08-01T12:00:00Z</date-time></dtstamp>\
+         <dtstart><date>2000-10-05</date></dtstart>\
+         <summary><parameters><language><text>de</text></language></parameters>\
+         <text>Birthday: Doe, J. &lt;J&amp;D&gt;</text></summary>\
+         </properties></vevent></components>\
+         </vcalendar></icalendar>\n"
+    )
+}
@@ -0,0 +1,196 @@
+#[cfg(test)]
+mod tests;
+
+use std::{fs, io::Cursor, time::SystemTime};
+
+use chrono::{DateTime, Datelike, DurationRound, Local, TimeZone, Utc};
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+use tiny_http::{Header, Method, Response, Server};
+
+use crate::{
+    config::Entry, event_years, extract_merged, input::find_input_files, merge_entries,
+    ser::calendar_to_string, wrap_events, EventExtractorError,
+};
+
+const CALENDARS_PATH: &str = "/calendars/";
+
+#[derive(Error, Debug)]
+pub enum ServeError {
+    #[error("binding to \"{}\" failed: {}", .0, .1)]
+    BindFailed(String, String),
+    #[error("std::io error")]
+    StdIoError(#[from] std::io::Error),
+}
+
+/// Calendar of a configuration entry generated for a request.
+#[derive(Debug)]
+pub struct ServedCalendar {
+    pub body: String,
+    pub etag: String,
+    /// Modification time of the newest input file or, if it is later, the
+    /// start of the current year, when the years of the events change.
+    pub last_modified: DateTime<Utc>,
+}
+
+/// Serves the calendars of the configuration entries on the given address
+/// until the server fails.
+pub fn serve(entries: &[Entry], bind: &str) -> Result<(), ServeError> {
+    let server =
+        Server::http(bind).map_err(|e| ServeError::BindFailed(bind.into(), e.to_string()))?;
+
+    log::info!("serving calendars on \"{}\"", server.server_addr());
+
+    for request in server.incoming_requests() {
+        log::debug!("{} {}", request.method(), request.url());
+
+        let response = respond(entries, request.method(), request.url(), request.headers());
+
+        request.respond(response)?;
+    }
+
+    Ok(())
+}
+
+/// Answers a request for `/calendars/<name>.ics`.
+///
+/// The calendar of an entry contains the events of all entries merged with
+/// it, see `merge_entries`.
+///
+/// Conditional requests are answered with "304 Not Modified" if the
+/// `If-None-Match` header matches the ETag or, without it, if the calendar
+/// was not modified since `If-Modified-Since`.
+pub fn respond(
+    entries: &[Entry],
+    method: &Method,
+    url: &str,
+    headers: &[Header],
+) -> Response<Cursor<Vec<u8>>> {
+    if *method != Method::Get && *method != Method::Head {
+        return Response::from_string("method not allowed")
+            .with_status_code(405)
+            .with_header(header("Allow", "GET, HEAD"));
+    }
+
+    let path = url.split('?').next().unwrap_or_default();
+    let merged = merge_entries(entries);
+    let entries = path
+        .strip_prefix(CALENDARS_PATH)
+        .and_then(|name| name.strip_suffix(".ics"))
+        .and_then(|name| {
+            merged
+                .iter()
+                .find(|entries| entries.iter().any(|entry| entry.calendar_name() == name))
+        });
+    let entries = match entries {
+        Some(entries) => entries,
+        None => return Response::from_string("not found").with_status_code(404),
+    };
+
+    let calendar = match render_calendar(entries) {
+        Ok(calendar) => calendar,
+        Err(e) => {
+            log::error!("generating calendar \"{}\" failed: {}", path, e);
+            return Response::from_string("internal server error").with_status_code(500);
+        }
+    };
+
+    let mut response = if is_not_modified(&calendar, headers) {
+        Response::from_string("").with_status_code(304)
+    } else {
+        Response::from_string(calendar.body)
+            .with_header(header("Content-Type", "text/calendar; charset=utf-8"))
+    };
+
+    response.add_header(header("ETag", &calendar.etag));
+    response.add_header(header(
+        "Last-Modified",
+        &calendar
+            .last_modified
+            .format("%a, %d %b %Y %H:%M:%S GMT")
+            .to_string(),
+    ));
+
+    response
+}
+
+/// Generates the calendar of configuration entries processed together with
+/// all their events, see `merge_entries`.
+///
+/// The DTSTAMP of the events is set to the modification time of the newest
+/// input file or the start of the current year, so that the calendar and its
+/// ETag only change with the contacts and the years of the events.
+pub fn render_calendar(entries: &[&Entry]) -> Result<ServedCalendar, EventExtractorError> {
+    let last_modified = last_modified(entries)?;
+    let (events, _) = extract_merged(entries, &event_years())?;
+    let mut events: Vec<_> = events.into_iter().map(|event| event.event).collect();
+
+    let timestamp = last_modified.format("%Y%m%dT%H%M%SZ").to_string();
+
+    for property in events
+        .iter_mut()
+        .flat_map(|event| event.properties.iter_mut())
+    {
+        if property.name == "DTSTAMP" {
+            property.value = Some(timestamp.clone());
+        }
+    }
+
+    let body = calendar_to_string(&wrap_events(events))?;
+    let etag = format!("\"{:x}\"", Sha256::digest(body.as_bytes()));
+
+    Ok(ServedCalendar {
+        body,
+        etag,
+        last_modified,
+    })
+}
+
+fn last_modified(entries: &[&Entry]) -> Result<DateTime<Utc>, EventExtractorError> {
+    // the years of the events change at the start of the year, see
+    // `event_years`
+    let mut last_modified = Local
+        .with_ymd_and_hms(Local::now().year(), 1, 1, 0, 0, 0)
+        .earliest()
+        .map(SystemTime::from)
+        .unwrap_or(SystemTime::UNIX_EPOCH);
+
+    for entry in entries {
+        for path in find_input_files(entry)?.files {
+            if let Ok(modified) = fs::metadata(&path).and_then(|metadata| metadata.modified()) {
+                last_modified = last_modified.max(modified);
+            }
+        }
+    }
+
+    // HTTP dates have a resolution of seconds
+    let last_modified = DateTime::<Utc>::from(last_modified);
+
+    Ok(last_modified
+        .duration_trunc(chrono::Duration::seconds(1))
+        .unwrap_or(last_modified))
+}
+
+fn is_not_modified(calendar: &ServedCalendar, headers: &[Header]) -> bool {
+    if let Some(if_none_match) = find_header(headers, "If-None-Match") {
+        return if_none_match
+            .split(',')
+            .map(str::trim)
+            .any(|etag| etag == "*" || etag.trim_start_matches("W/") == calendar.etag);
+    }
+
+    find_header(headers, "If-Modified-Since")
+        .and_then(|since| DateTime::parse_from_rfc2822(since).ok())
+        .is_some_and(|since| calendar.last_modified <= since)
+}
+
+fn find_header<'a>(headers: &'a [Header], name: &'static str) -> Option<&'a str> {
+    headers
+        .iter()
+        .find(|header| header.field.equiv(name))
+        .map(|header| header.value.as_str())
+}
+
+fn header(name: &str, value: &str) -> Header {
+    Header::from_bytes(name.as_bytes(), value.as_bytes()).expect("valid header")
+}
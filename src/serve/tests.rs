@@ -0,0 +1,168 @@
+use std::{io::Read, path::Path};
+
+use super::*;
+use crate::testing::vcard;
+
+fn entries(input: &Path) -> Vec<Entry> {
+    fs::write(
+        input.join("contacts.vcf"),
+        "BEGIN:VCARD\r\nVERSION:3.0\r\nUID:test-uid\r\nFN:Test Person\r\nBDAY;VALUE=DATE:19901005\r\nEND:VCARD\r\n",
+    )
+    .unwrap();
+
+    vec![Entry {
+        name: Some("family".into()),
+        input: input.to_string_lossy().into(),
+        ..Default::default()
+    }]
+}
+
+fn get_header(response: &Response<Cursor<Vec<u8>>>, name: &'static str) -> Option<String> {
+    response
+        .headers()
+        .iter()
+        .find(|header| header.field.equiv(name))
+        .map(|header| header.value.to_string())
+}
+
+fn body(response: Response<Cursor<Vec<u8>>>) -> String {
+    let mut body = String::new();
+
+    response.into_reader().read_to_string(&mut body).unwrap();
+    body
+}
+
+#[test]
+fn respond_1() {
+    let dir = tempfile::tempdir().unwrap();
+    let entries = entries(dir.path());
+
+    let response = respond(&entries, &Method::Get, "/calendars/family.ics", &[]);
+    assert_eq!(response.status_code(), 200);
+    assert_eq!(
+        get_header(&response, "Content-Type").as_deref(),
+        Some("text/calendar; charset=utf-8")
+    );
+    assert!(get_header(&response, "Last-Modified").is_some());
+
+    let etag = get_header(&response, "ETag").unwrap();
+    let calendar = body(response);
+    assert!(calendar.starts_with("BEGIN:VCALENDAR\r\n"));
+    assert!(calendar.contains("UID:test-uid_bday_"));
+
+    // the calendar is stable between requests
+    let response = respond(&entries, &Method::Get, "/calendars/family.ics", &[]);
+    assert_eq!(get_header(&response, "ETag"), Some(etag.clone()));
+
+    let response = respond(
+        &entries,
+        &Method::Get,
+        "/calendars/family.ics",
+        &[header("If-None-Match", &etag)],
+    );
+    assert_eq!(response.status_code(), 304);
+    assert!(body(response).is_empty());
+}
+
+#[test]
+fn respond_2() {
+    let dir = tempfile::tempdir().unwrap();
+    let entries = entries(dir.path());
+
+    let response = respond(
+        &entries,
+        &Method::Get,
+        "/calendars/family.ics",
+        &[header("If-Modified-Since", "Fri, 01 Jan 2100 00:00:00 GMT")],
+    );
+    assert_eq!(response.status_code(), 304);
+
+    let response = respond(
+        &entries,
+        &Method::Get,
+        "/calendars/family.ics",
+        &[header("If-Modified-Since", "Thu, 01 Jan 1970 00:00:00 GMT")],
+    );
+    assert_eq!(response.status_code(), 200);
+
+    let response = respond(
+        &entries,
+        &Method::Get,
+        "/calendars/family.ics",
+        &[header("If-None-Match", "\"other\"")],
+    );
+    assert_eq!(response.status_code(), 200);
+}
+
+#[test]
+fn respond_errors() {
+    let dir = tempfile::tempdir().unwrap();
+    let entries = entries(dir.path());
+
+    assert_eq!(
+        respond(&entries, &Method::Get, "/calendars/other.ics", &[]).status_code(),
+        404
+    );
+    assert_eq!(
+        respond(&entries, &Method::Get, "/family.ics", &[]).status_code(),
+        404
+    );
+    assert_eq!(
+        respond(&entries, &Method::Post, "/calendars/family.ics", &[]).status_code(),
+        405
+    );
+}
+
+#[test]
+fn render_calendar_1() {
+    let dir = tempfile::tempdir().unwrap();
+    let entries = entries(dir.path());
+    let modified = Utc.with_ymd_and_hms(2000, 1, 1, 0, 0, 0).unwrap();
+
+    fs::File::options()
+        .write(true)
+        .open(dir.path().join("contacts.vcf"))
+        .unwrap()
+        .set_modified(modified.into())
+        .unwrap();
+
+    // the calendar changes with the years of its events
+    let calendar = render_calendar(&[&entries[0]]).unwrap();
+    assert_eq!(calendar.last_modified.year(), Local::now().year());
+
+    let response = respond(
+        &entries,
+        &Method::Get,
+        "/calendars/family.ics",
+        &[header("If-Modified-Since", &modified.to_rfc2822())],
+    );
+    assert_eq!(response.status_code(), 200);
+}
+
+#[test]
+fn render_calendar_2() {
+    let home = tempfile::tempdir().unwrap();
+    let work = tempfile::tempdir().unwrap();
+    let entries: Vec<Entry> = [&home, &work]
+        .iter()
+        .map(|input| Entry {
+            name: Some("family".into()),
+            input: input.path().to_string_lossy().into(),
+            output: "family.ics".into(),
+            deduplicate: true,
+            ..Default::default()
+        })
+        .collect();
+
+    fs::write(home.path().join("a.vcf"), vcard("a", "Jane Doe")).unwrap();
+    fs::write(work.path().join("b.vcf"), vcard("b", "Jane Doe")).unwrap();
+
+    let calendar = body(respond(
+        &entries,
+        &Method::Get,
+        "/calendars/family.ics",
+        &[],
+    ));
+    assert_eq!(calendar.matches("SUMMARY").count(), event_years().len());
+    assert!(!calendar.contains("UID:b_bday_"));
+}
@@ -0,0 +1,55 @@
+use std::{fs, path::Path};
+
+use chrono::NaiveDate;
+use ical::{parser::ical::component::IcalEvent, property::Property};
+
+use crate::{EventKind, ExtractedEvent};
+
+/// vCard of a contact born on October 5th, 1990.
+pub fn vcard(uid: &str, name: &str) -> String {
+    vcard_with_birthday(uid, name, "19901005")
+}
+
+/// vCard of a contact with the given birthday. The note is not used for the
+/// events.
+pub fn vcard_with_birthday(uid: &str, name: &str, birthday: &str) -> String {
+    format!(
+        "BEGIN:VCARD\r\nVERSION:3.0\r\nUID:{}\r\nFN:{}\r\nNOTE:note\r\nBDAY;VALUE=DATE:{}\r\nEND:VCARD\r\n",
+        uid, name, birthday
+    )
+}
+
+/// Birthday event of a contact whose UID is its name.
+pub fn event(name: &str, date: NaiveDate, age: Option<i32>) -> ExtractedEvent {
+    ExtractedEvent {
+        uid: format!("{}_bday_{}", name, date.format("%Y")),
+        contact_uid: name.into(),
+        name: name.into(),
+        kind: EventKind::Birthday,
+        date,
+        age,
+        source: "/path/input/test.vcf".into(),
+        event: IcalEvent {
+            properties: vec![Property {
+                name: "SUMMARY".into(),
+                params: None,
+                value: Some(format!("Birthday: {}", name)),
+            }],
+            alarms: Vec::new(),
+        },
+    }
+}
+
+/// UIDs of the contacts with event files in an output directory.
+pub fn output_files(output: &Path) -> Vec<String> {
+    let mut files: Vec<String> = fs::read_dir(output)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+        .filter(|name| name.ends_with(".ics"))
+        .map(|name| name.split("_bday_").next().unwrap().to_string())
+        .collect();
+
+    files.sort();
+    files.dedup();
+    files
+}
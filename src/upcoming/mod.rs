@@ -0,0 +1,74 @@
+#[cfg(test)]
+mod tests;
+
+use chrono::NaiveDate;
+
+use crate::ExtractedEvent;
+
+/// Selects the events from today up to the given number of days ahead,
+/// sorted by date.
+pub fn upcoming_events(
+    events: &[ExtractedEvent],
+    today: NaiveDate,
+    days: i64,
+) -> Vec<&ExtractedEvent> {
+    let mut upcoming: Vec<&ExtractedEvent> = events
+        .iter()
+        .filter(|event| (0..=days).contains(&(event.date - today).num_days()))
+        .collect();
+
+    upcoming.sort_by(|a, b| (a.date, &a.name).cmp(&(b.date, &b.name)));
+    upcoming
+}
+
+/// Formats events as a table with the columns date, weekday, name, age and
+/// days left.
+pub fn format_table(events: &[&ExtractedEvent], today: NaiveDate) -> String {
+    let header = [
+        "Date".to_string(),
+        "Weekday".to_string(),
+        "Name".to_string(),
+        "Age".to_string(),
+        "Days".to_string(),
+    ];
+    let rows: Vec<[String; 5]> = events
+        .iter()
+        .map(|event| {
+            [
+                event.date.format("%Y-%m-%d").to_string(),
+                event.date.format("%a").to_string(),
+                event.name.clone(),
+                event.age.map(|age| age.to_string()).unwrap_or_default(),
+                (event.date - today).num_days().to_string(),
+            ]
+        })
+        .collect();
+    let widths: Vec<usize> = (0..header.len())
+        .map(|column| {
+            rows.iter()
+                .chain(std::iter::once(&header))
+                .map(|row| row[column].chars().count())
+                .max()
+                .unwrap_or_default()
+        })
+        .collect();
+
+    std::iter::once(&header)
+        .chain(rows.iter())
+        .map(|row| {
+            row.iter()
+                .zip(&widths)
+                .enumerate()
+                .map(|(column, (cell, width))| match column {
+                    // right-align numbers
+                    3 | 4 => format!("{:>width$}", cell, width = width),
+                    _ => format!("{:<width$}", cell, width = width),
+                })
+                .collect::<Vec<String>>()
+                .join("  ")
+                .trim_end()
+                .to_string()
+                + "\n"
+        })
+        .collect()
+}
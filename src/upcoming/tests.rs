@@ -0,0 +1,41 @@
+use super::*;
+use crate::testing::event;
+
+#[test]
+fn upcoming_events_1() {
+    let today = NaiveDate::from_ymd_opt(2023, 12, 20).unwrap();
+    let events = vec![
+        event("Later", NaiveDate::from_ymd_opt(2024, 1, 4).unwrap(), None),
+        event("Past", NaiveDate::from_ymd_opt(2023, 12, 19).unwrap(), None),
+        event("Today", today, Some(30)),
+        event(
+            "Too Late",
+            NaiveDate::from_ymd_opt(2024, 1, 5).unwrap(),
+            None,
+        ),
+    ];
+
+    assert_eq!(
+        upcoming_events(&events, today, 15)
+            .iter()
+            .map(|event| event.name.as_str())
+            .collect::<Vec<&str>>(),
+        vec!["Today", "Later"]
+    )
+}
+
+#[test]
+fn format_table_1() {
+    let today = NaiveDate::from_ymd_opt(2023, 12, 20).unwrap();
+    let events = [
+        event("Test Person", today, Some(30)),
+        event("Other", NaiveDate::from_ymd_opt(2024, 1, 4).unwrap(), None),
+    ];
+
+    assert_eq!(
+        format_table(&events.iter().collect::<Vec<_>>(), today),
+        "Date        Weekday  Name         Age  Days\n\
+         2023-12-20  Wed      Test Person   30     0\n\
+         2024-01-04  Thu      Other               15\n"
+    )
+}
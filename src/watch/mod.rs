@@ -0,0 +1,348 @@
+#[cfg(test)]
+mod tests;
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs::remove_file,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+    sync::mpsc::{channel, Receiver, RecvTimeoutError},
+    time::Duration,
+};
+
+use notify::{Event, RecursiveMode, Watcher};
+use thiserror::Error;
+
+use crate::{
+    config::Entry,
+    dav::is_collection_url,
+    dedupe::deduplicate_entry,
+    event_file_path, event_years, extract_file,
+    filter::{ContactFilter, Group},
+    groups::write_group_outputs,
+    input::{find_input_files, InputFilter, STDIN},
+    lock::lock_output,
+    merge_entries,
+    parallel::map_parallel,
+    report::{Diagnostic, Report},
+    write_event_file, write_events, writes_event_files, EventExtractorError, ExtractedEvent,
+};
+
+/// Time without further changes after which the changes are processed.
+pub const DEBOUNCE: Duration = Duration::from_millis(500);
+
+#[derive(Error, Debug)]
+pub enum WatchError {
+    #[error("notify error")]
+    NotifyError(#[from] notify::Error),
+    #[error("the watcher stopped unexpectedly")]
+    Disconnected,
+}
+
+/// Events of configuration entries which are processed together (see
+/// `merge_entries`) by the input file they were extracted from.
+pub struct WatchedEntry<'a> {
+    /// Entry whose output and settings are used.
+    config_entry: &'a Entry,
+    inputs: Vec<WatchedInput<'a>>,
+    /// UIDs of the events in the output, or `None` if it was not written yet.
+    written: Option<BTreeSet<String>>,
+    /// Input files whose events were not written yet.
+    pending: BTreeSet<PathBuf>,
+}
+
+/// Events and groups of a single configuration entry by input file.
+struct WatchedInput<'a> {
+    config_entry: &'a Entry,
+    filter: InputFilter,
+    contact_filter: ContactFilter,
+    events: BTreeMap<PathBuf, Vec<ExtractedEvent>>,
+    groups: BTreeMap<PathBuf, Vec<Group>>,
+}
+
+impl<'a> WatchedEntry<'a> {
+    /// Extracts and writes the events of all input files of the entries.
+    pub fn new(entries: &[&'a Entry]) -> Result<Self, EventExtractorError> {
+        let years = event_years();
+        let mut report = Report::default();
+        let mut inputs = Vec::new();
+
+        for &config_entry in entries {
+            let contact_filter = ContactFilter::new(config_entry)?;
+            let input_files = find_input_files(config_entry)?;
+            let mut events = BTreeMap::new();
+            let mut groups = BTreeMap::new();
+
+            report.diagnostics.extend(input_files.diagnostics);
+
+            let results = map_parallel(&input_files.files, config_entry.jobs(), |path| {
+                extract_file(config_entry, path, &years, &contact_filter)
+            });
+
+            for (path, result) in input_files.files.into_iter().zip(results) {
+                let (file_events, file_report) = result?;
+
+                events.insert(path.clone(), file_events);
+                groups.insert(path, file_report.groups.clone());
+                report.merge(file_report);
+            }
+
+            inputs.push(WatchedInput {
+                config_entry,
+                filter: InputFilter::new(config_entry)?,
+                contact_filter,
+                events,
+                groups,
+            });
+        }
+
+        let mut watched_entry = WatchedEntry {
+            config_entry: entries[0],
+            inputs,
+            written: None,
+            pending: BTreeSet::new(),
+        };
+
+        watched_entry.write(&mut report)?;
+        log::info!("{}", report);
+
+        Ok(watched_entry)
+    }
+
+    /// Regenerates the events of the given input files, which may have been
+    /// created, changed or removed. Paths which are not input files of the
+    /// entries are ignored, files which cannot be parsed keep their previous
+    /// events.
+    pub fn update(&mut self, paths: &BTreeSet<PathBuf>) -> Result<(), EventExtractorError> {
+        let years = event_years();
+        let mut report = Report::default();
+        let mut extracted = Vec::new();
+
+        for (index, input) in self.inputs.iter().enumerate() {
+            for path in paths.iter().filter(|path| input.filter.matches(path)) {
+                log::info!("input \"{}\" changed", path.to_string_lossy());
+
+                let (file_events, file_groups) = if path.is_file() {
+                    match extract_file(input.config_entry, path, &years, &input.contact_filter) {
+                        Ok((file_events, file_report)) => {
+                            let file_groups = file_report.groups.clone();
+
+                            report.merge(file_report);
+                            (file_events, file_groups)
+                        }
+                        // the previous events of the file are kept until it
+                        // can be read again, e.g. after it was written
+                        // completely
+                        Err(e) => {
+                            let diagnostic = Diagnostic::UnreadableInput {
+                                path: path.clone(),
+                                message: e.to_string(),
+                            };
+
+                            log::warn!("{}", diagnostic);
+                            report.diagnostics.push(diagnostic);
+                            continue;
+                        }
+                    }
+                } else {
+                    (Vec::new(), Vec::new())
+                };
+
+                extracted.push((index, path, file_events, file_groups));
+            }
+        }
+
+        if extracted.is_empty() {
+            return Ok(());
+        }
+
+        // a contact may move between files, so the events of all files are
+        // updated before any event file is removed
+        for (index, path, file_events, file_groups) in extracted {
+            let input = &mut self.inputs[index];
+
+            input.events.remove(path);
+            input.groups.remove(path);
+            self.pending.insert(path.clone());
+
+            if !file_events.is_empty() {
+                input.events.insert(path.clone(), file_events);
+            }
+            if !file_groups.is_empty() {
+                input.groups.insert(path.clone(), file_groups);
+            }
+        }
+
+        self.write(&mut report)?;
+        log::info!("{}", report);
+
+        Ok(())
+    }
+
+    /// Writes the events into the output while holding its lock.
+    ///
+    /// If the output is locked and the entry's `lock` behaviour is to skip
+    /// it, the events are written with the next change.
+    fn write(&mut self, report: &mut Report) -> Result<(), EventExtractorError> {
+        let events = self.events(report);
+
+        report.events = events.len();
+
+        let Some(_lock) = lock_output(self.config_entry)? else {
+            let diagnostic = Diagnostic::OutputLocked {
+                path: PathBuf::from(&self.config_entry.output),
+            };
+
+            log::warn!("{}", diagnostic);
+            report.diagnostics.push(diagnostic);
+            return Ok(());
+        };
+
+        match &self.written {
+            Some(written) if writes_event_files(self.config_entry) => {
+                self.replace_event_files(written, &events)?
+            }
+            _ => write_events(self.config_entry, &events, report)?,
+        }
+        write_group_outputs(self.config_entry, &events, &self.groups(), report)?;
+
+        self.written = Some(events.into_iter().map(|event| event.uid).collect());
+        self.pending.clear();
+
+        Ok(())
+    }
+
+    /// Removes the event files of the events which no longer exist and
+    /// writes the event files which are new or of changed input files.
+    fn replace_event_files(
+        &self,
+        written: &BTreeSet<String>,
+        events: &[ExtractedEvent],
+    ) -> Result<(), EventExtractorError> {
+        let uids: BTreeSet<&String> = events.iter().map(|event| &event.uid).collect();
+
+        for uid in written.iter().filter(|uid| !uids.contains(uid)) {
+            match remove_file(event_file_path(self.config_entry, uid)) {
+                Err(e) if e.kind() != ErrorKind::NotFound => return Err(e.into()),
+                _ => {}
+            }
+        }
+
+        for event in events {
+            if self.pending.contains(&event.source) || !written.contains(&event.uid) {
+                write_event_file(self.config_entry, event)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Events of all input files, deduplicated like those of a single run and
+    /// sorted by date and identifier.
+    fn events(&self, report: &mut Report) -> Vec<ExtractedEvent> {
+        let events = self
+            .inputs
+            .iter()
+            .flat_map(|input| {
+                input.contact_filter.filter_groups(
+                    input.events.values().flatten().cloned().collect(),
+                    &input.groups.values().flatten().cloned().collect::<Vec<_>>(),
+                )
+            })
+            .collect();
+        let mut events = deduplicate_entry(self.config_entry, events, report);
+
+        events.sort_by(|a, b| (a.date, &a.uid).cmp(&(b.date, &b.uid)));
+        events
+    }
+
+    /// Groups of all input files.
+    fn groups(&self) -> Vec<Group> {
+        self.inputs
+            .iter()
+            .flat_map(|input| input.groups.values().flatten().cloned())
+            .collect()
+    }
+}
+
+/// Processes the configuration entries and regenerates their events
+/// whenever their input files change.
+///
+/// Entries are merged like in a single run, see `merge_entries`.
+pub fn watch(entries: &[Entry]) -> Result<(), EventExtractorError> {
+    let (sender, receiver) = channel();
+    let mut watcher = notify::recommended_watcher(sender).map_err(WatchError::from)?;
+    let mut watched_entries = Vec::new();
+
+    for merged in merge_entries(entries) {
+        let mut watchable = Vec::new();
+
+        for entry in merged {
+            if entry.input == STDIN || is_collection_url(&entry.input) {
+                log::warn!("input \"{}\" cannot be watched", entry.input);
+                continue;
+            }
+
+            // editors often replace files, so the directory of a single input
+            // file is watched instead of the file itself
+            let input = Path::new(&entry.input);
+            let (path, mode) = match input.is_file() {
+                true => (
+                    input
+                        .parent()
+                        .filter(|parent| !parent.as_os_str().is_empty())
+                        .unwrap_or(Path::new(".")),
+                    RecursiveMode::NonRecursive,
+                ),
+                false if entry.recursive => (input, RecursiveMode::Recursive),
+                false => (input, RecursiveMode::NonRecursive),
+            };
+
+            watcher.watch(path, mode).map_err(WatchError::from)?;
+            log::info!("watching \"{}\"", path.to_string_lossy());
+            watchable.push(entry);
+        }
+
+        if watchable.is_empty() {
+            continue;
+        }
+
+        match WatchedEntry::new(&watchable) {
+            Ok(watched_entry) => watched_entries.push(watched_entry),
+            Err(e) => log::error!("{}", e),
+        }
+    }
+
+    loop {
+        let paths = debounce(&receiver, DEBOUNCE)?;
+
+        for watched_entry in &mut watched_entries {
+            if let Err(e) = watched_entry.update(&paths) {
+                log::error!("{}", e);
+            }
+        }
+    }
+}
+
+/// Waits for a change and collects the paths of all changes until there
+/// was no change for the given time.
+pub fn debounce(
+    receiver: &Receiver<notify::Result<Event>>,
+    debounce: Duration,
+) -> Result<BTreeSet<PathBuf>, WatchError> {
+    let mut paths = BTreeSet::new();
+    let mut event = receiver.recv().map_err(|_| WatchError::Disconnected);
+
+    loop {
+        match event? {
+            Ok(event) => paths.extend(event.paths),
+            Err(e) => log::warn!("watch error: {}", e),
+        }
+
+        event = match receiver.recv_timeout(debounce) {
+            Ok(event) => Ok(event),
+            Err(RecvTimeoutError::Timeout) => return Ok(paths),
+            Err(RecvTimeoutError::Disconnected) => Err(WatchError::Disconnected),
+        };
+    }
+}
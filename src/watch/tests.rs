@@ -0,0 +1,252 @@
+use std::{fs, sync::mpsc::Sender};
+
+use crate::{
+    config::{GroupOutput, LockBehavior, OutputFormat},
+    testing::{output_files, vcard},
+};
+
+use super::*;
+
+#[test]
+fn update_1() {
+    let input = tempfile::tempdir().unwrap();
+    let output = tempfile::tempdir().unwrap();
+    let entry = Entry {
+        input: input.path().to_string_lossy().into(),
+        output: output.path().to_string_lossy().into(),
+        ..Default::default()
+    };
+
+    fs::write(input.path().join("a.vcf"), vcard("a", "A")).unwrap();
+    fs::write(input.path().join("b.vcf"), vcard("b", "B")).unwrap();
+
+    let mut watched_entry = WatchedEntry::new(&[&entry]).unwrap();
+    assert_eq!(output_files(output.path()), vec!["a", "b"]);
+
+    fs::write(input.path().join("a.vcf"), vcard("c", "C")).unwrap();
+    fs::remove_file(input.path().join("b.vcf")).unwrap();
+    fs::write(input.path().join("notes.txt"), vcard("d", "D")).unwrap();
+    watched_entry
+        .update(&BTreeSet::from([
+            input.path().join("a.vcf"),
+            input.path().join("b.vcf"),
+            input.path().join("notes.txt"),
+        ]))
+        .unwrap();
+
+    assert_eq!(output_files(output.path()), vec!["c"]);
+}
+
+#[test]
+fn update_2() {
+    let input = tempfile::tempdir().unwrap();
+    let output = tempfile::tempdir().unwrap();
+    let entry = Entry {
+        input: input.path().to_string_lossy().into(),
+        output: output.path().join("events.csv").to_string_lossy().into(),
+        output_format: OutputFormat::Csv,
+        ..Default::default()
+    };
+
+    fs::write(input.path().join("a.vcf"), vcard("a", "First Person")).unwrap();
+
+    let mut watched_entry = WatchedEntry::new(&[&entry]).unwrap();
+
+    fs::write(input.path().join("b.vcf"), vcard("b", "Second Person")).unwrap();
+    watched_entry
+        .update(&BTreeSet::from([input.path().join("b.vcf")]))
+        .unwrap();
+
+    let csv = fs::read_to_string(output.path().join("events.csv")).unwrap();
+    assert!(csv.contains("First Person"));
+    assert!(csv.contains("Second Person"));
+}
+
+#[test]
+fn update_3() {
+    let input = tempfile::tempdir().unwrap();
+    let output = tempfile::tempdir().unwrap();
+    let entry = Entry {
+        input: input.path().to_string_lossy().into(),
+        output: output.path().to_string_lossy().into(),
+        recursive: true,
+        ..Default::default()
+    };
+
+    fs::create_dir(input.path().join("a")).unwrap();
+    fs::create_dir(input.path().join("b")).unwrap();
+    fs::write(input.path().join("b/x.vcf"), vcard("x", "X")).unwrap();
+
+    let mut watched_entry = WatchedEntry::new(&[&entry]).unwrap();
+    assert_eq!(output_files(output.path()), vec!["x"]);
+
+    // the contact moves from "b" to "a", which is processed first
+    fs::rename(input.path().join("b/x.vcf"), input.path().join("a/x.vcf")).unwrap();
+    watched_entry
+        .update(&BTreeSet::from([
+            input.path().join("a/x.vcf"),
+            input.path().join("b/x.vcf"),
+        ]))
+        .unwrap();
+
+    assert_eq!(output_files(output.path()), vec!["x"]);
+}
+
+#[test]
+fn update_4() {
+    let input = tempfile::tempdir().unwrap();
+    let output = tempfile::tempdir().unwrap();
+    let entry = Entry {
+        input: input.path().to_string_lossy().into(),
+        output: output.path().to_string_lossy().into(),
+        lock: LockBehavior::Skip,
+        ..Default::default()
+    };
+
+    fs::write(input.path().join("a.vcf"), vcard("a", "A")).unwrap();
+
+    let mut watched_entry = WatchedEntry::new(&[&entry]).unwrap();
+
+    // changes are not written while another run holds the lock
+    let lock = lock_output(&entry).unwrap().unwrap();
+    fs::write(input.path().join("a.vcf"), vcard("c", "C")).unwrap();
+    watched_entry
+        .update(&BTreeSet::from([input.path().join("a.vcf")]))
+        .unwrap();
+    assert_eq!(output_files(output.path()), vec!["a"]);
+
+    drop(lock);
+    fs::write(input.path().join("b.vcf"), vcard("b", "B")).unwrap();
+    watched_entry
+        .update(&BTreeSet::from([input.path().join("b.vcf")]))
+        .unwrap();
+    assert_eq!(output_files(output.path()), vec!["b", "c"]);
+}
+
+#[test]
+fn update_5() {
+    let input = tempfile::tempdir().unwrap();
+    let output = tempfile::tempdir().unwrap();
+    let family = tempfile::tempdir().unwrap();
+    let entry = Entry {
+        input: input.path().to_string_lossy().into(),
+        output: output.path().to_string_lossy().into(),
+        group_outputs: vec![GroupOutput {
+            group: "Family".into(),
+            output: family.path().to_string_lossy().into(),
+        }],
+        ..Default::default()
+    };
+    let group = |members: &str| {
+        format!(
+            "BEGIN:VCARD\r\nVERSION:4.0\r\nKIND:group\r\nUID:family\r\nFN:Family\r\n{}END:VCARD\r\n",
+            members
+        )
+    };
+
+    fs::write(input.path().join("a.vcf"), vcard("a", "A")).unwrap();
+    fs::write(input.path().join("b.vcf"), vcard("b", "B")).unwrap();
+    fs::write(input.path().join("groups.vcf"), group("MEMBER:a\r\n")).unwrap();
+
+    let mut watched_entry = WatchedEntry::new(&[&entry]).unwrap();
+    assert_eq!(output_files(family.path()), vec!["a"]);
+
+    fs::write(
+        input.path().join("groups.vcf"),
+        group("MEMBER:a\r\nMEMBER:b\r\n"),
+    )
+    .unwrap();
+    watched_entry
+        .update(&BTreeSet::from([input.path().join("groups.vcf")]))
+        .unwrap();
+    assert_eq!(output_files(family.path()), vec!["a", "b"]);
+}
+
+#[test]
+fn update_6() {
+    let home = tempfile::tempdir().unwrap();
+    let work = tempfile::tempdir().unwrap();
+    let output = tempfile::tempdir().unwrap();
+    let entry = |input: &Path| Entry {
+        input: input.to_string_lossy().into(),
+        output: output.path().to_string_lossy().into(),
+        deduplicate: true,
+        ..Default::default()
+    };
+    let entries = [entry(home.path()), entry(work.path())];
+
+    fs::write(home.path().join("a.vcf"), vcard("b", "Jane Doe")).unwrap();
+    fs::write(work.path().join("a.vcf"), vcard("a", "jane doe")).unwrap();
+
+    let merged = merge_entries(&entries);
+    let mut watched_entry = WatchedEntry::new(&merged[0]).unwrap();
+    assert_eq!(output_files(output.path()), vec!["a"]);
+
+    // the duplicate takes over when the kept contact is removed
+    fs::remove_file(work.path().join("a.vcf")).unwrap();
+    watched_entry
+        .update(&BTreeSet::from([work.path().join("a.vcf")]))
+        .unwrap();
+    assert_eq!(output_files(output.path()), vec!["b"]);
+}
+
+#[test]
+fn debounce_1() {
+    let (sender, receiver): (Sender<notify::Result<Event>>, _) = channel();
+
+    sender
+        .send(Ok(Event::default().add_path("/path/input/a.vcf".into())))
+        .unwrap();
+    sender
+        .send(Ok(Event::default().add_path("/path/input/b.vcf".into())))
+        .unwrap();
+    sender
+        .send(Ok(Event::default().add_path("/path/input/a.vcf".into())))
+        .unwrap();
+
+    assert_eq!(
+        debounce(&receiver, Duration::from_millis(10)).unwrap(),
+        BTreeSet::from([
+            PathBuf::from("/path/input/a.vcf"),
+            PathBuf::from("/path/input/b.vcf")
+        ])
+    );
+
+    drop(sender);
+    assert!(matches!(
+        debounce(&receiver, Duration::from_millis(10)),
+        Err(WatchError::Disconnected)
+    ));
+}
+
+#[test]
+fn update_7() {
+    let input = tempfile::tempdir().unwrap();
+    let output = tempfile::tempdir().unwrap();
+    let entry = Entry {
+        input: input.path().to_string_lossy().into(),
+        output: output.path().to_string_lossy().into(),
+        ..Default::default()
+    };
+
+    fs::write(input.path().join("a.vcf"), vcard("a", "A")).unwrap();
+    fs::write(input.path().join("b.vcf"), vcard("b", "B")).unwrap();
+
+    let mut watched_entry = WatchedEntry::new(&[&entry]).unwrap();
+
+    // b.vcf is only written partially
+    fs::write(input.path().join("a.vcf"), vcard("c", "C")).unwrap();
+    fs::write(
+        input.path().join("b.vcf"),
+        "BEGIN:VCARD\r\nVERSION:3.0\r\nUID:b\r\n",
+    )
+    .unwrap();
+    watched_entry
+        .update(&BTreeSet::from([
+            input.path().join("a.vcf"),
+            input.path().join("b.vcf"),
+        ]))
+        .unwrap();
+
+    assert_eq!(output_files(output.path()), vec!["b", "c"]);
+}
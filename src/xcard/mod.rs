@@ -0,0 +1,144 @@
+#[cfg(test)]
+mod tests;
+
+use ical::{parser::vcard::component::VcardContact, property::Property};
+use roxmltree::{Document, Node};
+use thiserror::Error;
+
+use crate::jcard::{escape_text, value_type_param};
+
+const NAMESPACE: &str = "urn:ietf:params:xml:ns:vcard-4.0";
+const VALUE_TYPES: [&str; 11] = [
+    "text",
+    "uri",
+    "date",
+    "time",
+    "date-time",
+    "date-and-or-time",
+    "timestamp",
+    "boolean",
+    "integer",
+    "float",
+    "utc-offset",
+];
+
+#[derive(Error, Debug)]
+pub enum XCardError {
+    #[error("invalid xCard: {}", .0)]
+    InvalidXCard(String),
+    #[error("roxmltree error")]
+    XmlError(#[from] roxmltree::Error),
+}
+
+/// Parses an xCard (RFC 6351) document.
+///
+/// Properties are converted into their vCard representation, i.e. text
+/// values are escaped. Elements outside of the vCard namespace are ignored.
+pub fn parse_xcard(text: &str) -> Result<Vec<VcardContact>, XCardError> {
+    let document = Document::parse(text)?;
+    let root = document.root_element();
+
+    match root.tag_name().name() {
+        "vcards" => vcard_elements(root).map(parse_contact).collect(),
+        "vcard" => Ok(vec![parse_contact(root)?]),
+        name => Err(XCardError::InvalidXCard(format!(
+            "unexpected root element \"{}\"",
+            name
+        ))),
+    }
+}
+
+fn parse_contact(contact: Node) -> Result<VcardContact, XCardError> {
+    let mut properties = Vec::new();
+
+    for element in vcard_elements(contact) {
+        match element.tag_name().name() {
+            "group" => {
+                let group = element
+                    .attribute("name")
+                    .ok_or(XCardError::InvalidXCard("group without name".into()))?;
+
+                for property in vcard_elements(element) {
+                    let mut property = parse_property(property);
+
+                    property.name = format!("{}.{}", group, property.name);
+                    properties.push(property);
+                }
+            }
+            _ => properties.push(parse_property(element)),
+        }
+    }
+
+    Ok(VcardContact { properties })
+}
+
+fn parse_property(property: Node) -> Property {
+    let mut params = Vec::new();
+    let mut values: Vec<(&str, String)> = Vec::new();
+
+    for element in vcard_elements(property) {
+        match element.tag_name().name() {
+            "parameters" => {
+                for param in vcard_elements(element) {
+                    params.push((
+                        param.tag_name().name().to_uppercase(),
+                        vcard_elements(param).map(text).collect(),
+                    ));
+                }
+            }
+            name => values.push((name, text(element))),
+        }
+    }
+
+    let value = match values.first() {
+        Some((value_type, _)) if VALUE_TYPES.contains(value_type) => {
+            if *value_type != "text" {
+                let strings: Vec<&str> = values.iter().map(|(_, value)| value.as_str()).collect();
+
+                params.push(("VALUE".into(), vec![value_type_param(value_type, &strings)]));
+            }
+
+            values
+                .iter()
+                .map(|(value_type, value)| match *value_type {
+                    "text" => escape_text(value),
+                    _ => value.clone(),
+                })
+                .collect::<Vec<String>>()
+                .join(",")
+        }
+        // structured values (e.g. "N" or "ADR"), repeated components hold
+        // several values
+        _ => {
+            let mut components: Vec<(&str, Vec<String>)> = Vec::new();
+
+            for (name, value) in values {
+                match components.last_mut() {
+                    Some((last, items)) if *last == name => items.push(escape_text(&value)),
+                    _ => components.push((name, vec![escape_text(&value)])),
+                }
+            }
+
+            components
+                .iter()
+                .map(|(_, items)| items.join(","))
+                .collect::<Vec<String>>()
+                .join(";")
+        }
+    };
+
+    Property {
+        name: property.tag_name().name().to_uppercase(),
+        params: (!params.is_empty()).then_some(params),
+        value: Some(value),
+    }
+}
+
+fn vcard_elements<'a, 'input>(node: Node<'a, 'input>) -> impl Iterator<Item = Node<'a, 'input>> {
+    node.children()
+        .filter(|child| child.is_element() && child.tag_name().namespace() == Some(NAMESPACE))
+}
+
+fn text(node: Node) -> String {
+    node.text().unwrap_or_default().to_string()
+}
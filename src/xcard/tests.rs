@@ -0,0 +1,108 @@
+use super::*;
+use crate::ExtractedDate;
+
+#[test]
+fn parse_xcard_1() {
+    let text = r#"<?xml version="1.0" encoding="UTF-8"?>
+        <vcards xmlns="urn:ietf:params:xml:ns:vcard-4.0">
+          <vcard>
+            <fn>
+              <parameters>
+                <language><language-tag>de</language-tag></language>
+                <type><text>work</text><text>home</text></type>
+              </parameters>
+              <text>Doe, J.</text>
+            </fn>
+            <n>
+              <surname>Doe</surname>
+              <given>J.</given>
+              <additional/>
+              <prefix/>
+              <suffix>ing. jr</suffix>
+              <suffix>M.Sc.</suffix>
+            </n>
+            <bday><date>--0203</date></bday>
+            <group name="item1">
+              <tel><uri>tel:+1-555-555-5555</uri></tel>
+            </group>
+            <x-ignored xmlns="urn:example:other"><text>ignored</text></x-ignored>
+            <uid><uri>urn:uuid:test_uid</uri></uid>
+          </vcard>
+        </vcards>
+    "#;
+
+    assert_eq!(
+        format!("{:?}", parse_xcard(text).unwrap()),
+        format!(
+            "{:?}",
+            vec![VcardContact {
+                properties: vec![
+                    Property {
+                        name: "FN".into(),
+                        params: Some(vec![
+                            ("LANGUAGE".into(), vec!["de".into()]),
+                            ("TYPE".into(), vec!["work".into(), "home".into()])
+                        ]),
+                        value: Some("Doe\\, J.".into())
+                    },
+                    Property {
+                        name: "N".into(),
+                        params: None,
+                        value: Some("Doe;J.;;;ing. jr,M.Sc.".into())
+                    },
+                    Property {
+                        name: "BDAY".into(),
+                        params: Some(vec![("VALUE".into(), vec!["DATE".into()])]),
+                        value: Some("--0203".into())
+                    },
+                    Property {
+                        name: "item1.TEL".into(),
+                        params: Some(vec![("VALUE".into(), vec!["URI".into()])]),
+                        value: Some("tel:+1-555-555-5555".into())
+                    },
+                    Property {
+                        name: "UID".into(),
+                        params: Some(vec![("VALUE".into(), vec!["URI".into()])]),
+                        value: Some("urn:uuid:test_uid".into())
+                    },
+                ]
+            }]
+        )
+    )
+}
+
+#[test]
+fn parse_xcard_invalid() {
+    assert!(parse_xcard("<contacts/>").is_err())
+}
+
+#[test]
+fn parse_xcard_2() {
+    let text = r#"<?xml version="1.0" encoding="UTF-8"?>
+        <vcards xmlns="urn:ietf:params:xml:ns:vcard-4.0">
+          <vcard>
+            <fn><text>Test Person</text></fn>
+            <bday><date-and-or-time>19901005</date-and-or-time></bday>
+            <anniversary><date-and-or-time>20090808T1430-0500</date-and-or-time></anniversary>
+          </vcard>
+        </vcards>
+    "#;
+    let contact = &parse_xcard(text).unwrap()[0];
+
+    assert_eq!(
+        contact.properties[1].params,
+        Some(vec![("VALUE".into(), vec!["DATE".into()])])
+    );
+    assert_eq!(
+        contact.properties[2].params,
+        Some(vec![("VALUE".into(), vec!["DATE-AND-OR-TIME".into()])])
+    );
+    assert_eq!(
+        ExtractedDate::try_from(&contact.properties[1]).unwrap(),
+        ExtractedDate {
+            year: Some(1990),
+            month: 10,
+            day: 5
+        }
+    );
+}